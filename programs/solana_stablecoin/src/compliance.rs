@@ -0,0 +1,50 @@
+// compliance.rs
+use anchor_lang::prelude::*;
+use crate::states::{kyc_attestation::KycAttestation, stablecoin::{KycPolicy, MintPolicy, StablecoinState}};
+use crate::errors::StablecoinError;
+use crate::kyc::{verify_kyc, verify_kyc_requirements};
+
+/// Checks that `user` may hold a position in a stablecoin: KYC per its policy
+/// and requirements, and no denylist entry. `denylist_entry` is the user's
+/// denylist PDA, already derived by the caller's account constraints.
+/// Shared by mint_tokens, burn_tokens and their quotes.
+pub fn verify_holder(
+    stablecoin: &StablecoinState,
+    user: &Pubkey,
+    kyc_info: &AccountInfo,
+    kyc_attestation: Option<&KycAttestation>,
+    denylist_entry: &AccountInfo,
+) -> Result<()> {
+    // 1. Verify KYC using Etherfuse, per the stablecoin's policy
+    verify_kyc(kyc_info, user, stablecoin.kyc_policy)?;
+    verify_kyc_requirements(kyc_attestation, user, stablecoin)?;
+
+    // 2. Denylisted addresses can neither mint nor burn
+    require!(
+        denylist_entry.data_is_empty(),
+        StablecoinError::AddressDenylisted
+    );
+
+    Ok(())
+}
+
+/// Checks `user` against the stablecoin's mint policy. `allowlisted` is whether
+/// the caller resolved the user's MinterAllowlistEntry.
+pub fn verify_mint_policy(
+    stablecoin: &StablecoinState,
+    user: &Pubkey,
+    kyc_info: &AccountInfo,
+    allowlisted: bool,
+) -> Result<()> {
+    match stablecoin.mint_policy {
+        MintPolicy::Open => {}
+        MintPolicy::KycOnly => {
+            verify_kyc(kyc_info, user, KycPolicy::Required)?;
+        }
+        MintPolicy::AllowlistOnly => {
+            require!(allowlisted, StablecoinError::MinterNotAllowlisted);
+        }
+    }
+
+    Ok(())
+}
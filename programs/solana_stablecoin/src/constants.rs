@@ -5,10 +5,18 @@ use anchor_lang::prelude::*;
 pub const FACTORY_STATE_SEED: &[u8] = b"factory_state";
 pub const STABLECOIN_SEED: &[u8] = b"stablecoin";
 pub const USER_STATE_SEED: &[u8] = b"user_state";
+pub const SYMBOL_REGISTRY_SEED: &[u8] = b"symbol_registry";
+pub const STABLECOIN_INDEX_SEED: &[u8] = b"stablecoin_index";
+pub const DENYLIST_SEED: &[u8] = b"denylist";
+pub const MINTER_ALLOWLIST_SEED: &[u8] = b"minter_allowlist";
+pub const FX_FEED_SEED: &[u8] = b"fx_feed";
+pub const KYC_ATTESTATION_SEED: &[u8] = b"kyc_attestation";
 
 pub const PRICE_SCALE: u128 = 1_000_000;  // 6 decimals of precision
 pub const BPS_SCALE: u16 = 10_000;        // Basis points (100% = 10000)
+pub const RATE_INDEX_SCALE: u128 = 1_000_000_000_000;  // 12 decimals for cumulative rate indexes
 pub const PROTOCOL_FEE_BPS: u16 = 1_000;
+pub const REDEMPTION_FEE_BPS: u16 = 50;    // 0.5% fee on peg redemptions
 
 // Collateral ratio constants (in basis points)
 pub const MIN_ALLOWED_COLLATERAL_RATIO: u16 = 12_000;  // 120% minimum collateral ratio
@@ -20,7 +28,7 @@ pub const MIN_FEE_RATE_BPS: u16 = 10;                 // 0.01% minimum fee rate
 pub const MAX_FEE_RATE_BPS: u16 = 10_000;          // 100% maximum fee rate
 pub const DEFAULT_BASE_FEE_RATE_BPS: u16 = 30;      // 0.3% default fee rate
 
-pub const BASE_INTEREST_RATE: u64 = 500; // 5% base interest rate
+pub const BASE_INTEREST_RATE: u16 = 500; // 5% base annual stability fee
 
 // For regular rate updates
 pub const RATE_UPDATE_INTERVAL: i64 = 7 * 24 * 60 * 60;  // 1 week
@@ -31,6 +39,10 @@ pub const RATE_FRESHNESS_THRESHOLD: i64 = 15 * 60;  // 15 minutes
 // Buffer sizes
 pub const RESERVE_SPACE: usize = 32;               // Reserved space for future upgrades
 
+// Account schema versions, bumped only when a layout changes
+pub const FACTORY_SCHEMA_VERSION: u16 = 2;
+pub const STABLECOIN_SCHEMA_VERSION: u16 = 2;
+
 // Maximum allowed bonds
 pub const MAX_ALLOWED_BONDS: usize = 10;
 
@@ -42,13 +54,37 @@ pub const MIN_YIELD_COLLECTION_INTERVAL: i64 = 7 * 24 * 60 * 60; // 7 days in se
 pub const REBASE_INTERVAL: i64 = 7 * 24 * 60 * 60; // 1 week in seconds
 pub const YEAR_IN_SECONDS: i64 = 365 * 24 * 60 * 60;
 
-// USDC mint
+// USDC mint, the required fee mint when built with the `mainnet` feature
 pub const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
 
 // Stablecoin limits
 pub const STABLECOIN_DECIMALS: u8 = 6;           // Decimal places for stablecoin
+pub const STABLECOIN_SCALE: u64 = 1_000_000;     // 10^STABLECOIN_DECIMALS
+pub const MAX_URI_LENGTH: usize = 200;           // Metaplex metadata URI limit
+
+// ISO-4217 currencies a stablecoin can target
+pub const SUPPORTED_TARGET_CURRENCIES: [&str; 5] = ["USD", "MXN", "BRL", "EUR", "GBP"];
 
 pub const ORACLE_STALENESS_THRESHOLD: i64 = 300; // 5 minutes in seconds, 600 for 10 minutes
 
+// Bounds for admin-configurable protocol parameters
+pub const MIN_ORACLE_STALENESS_THRESHOLD: i64 = 30;             // 30 seconds
+pub const MAX_ORACLE_STALENESS_THRESHOLD: i64 = 60 * 60;        // 1 hour
+pub const MAX_RATE_FRESHNESS_THRESHOLD: i64 = 24 * 60 * 60;     // 1 day
+pub const MIN_REBASE_INTERVAL: i64 = 60 * 60;                   // 1 hour
+pub const MAX_REBASE_INTERVAL: i64 = 30 * 24 * 60 * 60;         // 30 days
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 5_000;                    // 50% of yield
+pub const MAX_REDEMPTION_FEE_BPS: u16 = 1_000;                  // 10%
+pub const MAX_STABILITY_FEE_BPS: u16 = 2_000;                   // 20% a year
+pub const STABILITY_FEE_ACCRUAL_INTERVAL: i64 = 60 * 60;        // Stability fees accrue at most hourly
+
+// Mint and redemption flow limits
+pub const MIN_FLOW_WINDOW: i64 = 60 * 60;                       // 1 hour
+pub const MAX_FLOW_WINDOW: i64 = 30 * 24 * 60 * 60;             // 30 days
+pub const FLOW_LIMIT_WARNING_BPS: u16 = 9_000;                  // FlowLimitApproached at 90% of a cap
+
+// KYC requirements
+pub const MAX_KYC_JURISDICTIONS: usize = 8;                     // Allowed jurisdictions per stablecoin
+
 pub const SOL_FEE_VAULT_SEED: &[u8] = b"sol_fee_vault";
 
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::states::stablecoin::StablecoinState;
+use crate::errors::StablecoinError;
+use crate::events::*;
+
+/// AcceptStablecoinAuthority completes a two-step authority transfer.
+/// Must be signed by the pending authority.
+#[derive(Accounts)]
+pub struct AcceptStablecoinAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.pending_authority.is_some() @ StablecoinError::NoPendingAuthority,
+        constraint = stablecoin_state.pending_authority == Some(new_authority.key()) @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+}
+
+pub fn accept_stablecoin_authority(
+    ctx: Context<AcceptStablecoinAuthority>,
+) -> Result<()> {
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let previous_authority = stablecoin.authority;
+
+    stablecoin.authority = ctx.accounts.new_authority.key();
+    stablecoin.pending_authority = None;
+    stablecoin.last_updated = Clock::get()?.unix_timestamp;
+
+    emit!(StablecoinAuthorityTransferred {
+        stablecoin: stablecoin.key(),
+        previous_authority,
+        new_authority: stablecoin.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, TokenInterface},
+};
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// AccrueStabilityFee brings a stablecoin's stability fee up to date and mints
+/// it to the protocol fee vault. Anyone can call it; every instruction that
+/// changes positions or supply also accrues.
+#[derive(Accounts)]
+pub struct AccrueStabilityFee<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        address = stablecoin_state.mint
+    )]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accrues the stability fee on every position and mints it to the fee vault.
+/// Returns the fee minted.
+pub fn collect_stability_fee<'info>(
+    stablecoin_state: &mut Account<'info, StablecoinState>,
+    stablecoin_mint: AccountInfo<'info>,
+    stability_fee_vault: AccountInfo<'info>,
+    stablecoin_token_program: AccountInfo<'info>,
+) -> Result<u64> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let fee = stablecoin_state.accrue_stability_fee(timestamp)?;
+    mint_stability_fee(stablecoin_state, fee, timestamp, stablecoin_mint, stability_fee_vault, stablecoin_token_program)
+}
+
+/// Like collect_stability_fee, but accrues through now even inside the
+/// accrual interval, for callers about to change the rate or end accrual
+pub fn settle_stability_fee<'info>(
+    stablecoin_state: &mut Account<'info, StablecoinState>,
+    stablecoin_mint: AccountInfo<'info>,
+    stability_fee_vault: AccountInfo<'info>,
+    stablecoin_token_program: AccountInfo<'info>,
+) -> Result<u64> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let fee = stablecoin_state.accrue_stability_fee_through(timestamp)?;
+    mint_stability_fee(stablecoin_state, fee, timestamp, stablecoin_mint, stability_fee_vault, stablecoin_token_program)
+}
+
+fn mint_stability_fee<'info>(
+    stablecoin_state: &Account<'info, StablecoinState>,
+    fee: u64,
+    timestamp: i64,
+    stablecoin_mint: AccountInfo<'info>,
+    stability_fee_vault: AccountInfo<'info>,
+    stablecoin_token_program: AccountInfo<'info>,
+) -> Result<u64> {
+    if fee == 0 {
+        return Ok(0);
+    }
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            stablecoin_token_program,
+            token_interface::MintTo {
+                mint: stablecoin_mint,
+                to: stability_fee_vault,
+                authority: stablecoin_state.to_account_info(),
+            },
+            &[&[
+                STABLECOIN_SEED,
+                stablecoin_state.creator.as_ref(),
+                stablecoin_state.original_symbol.as_bytes(),
+                &[stablecoin_state.bump],
+            ]]
+        ),
+        fee
+    )?;
+
+    emit!(StabilityFeeAccrued {
+        stablecoin: stablecoin_state.key(),
+        fee,
+        fee_index: stablecoin_state.stability_fee_index,
+        timestamp,
+    });
+
+    Ok(fee)
+}
+
+pub fn accrue_stability_fee(
+    ctx: Context<AccrueStabilityFee>,
+) -> Result<()> {
+    collect_stability_fee(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        ctx.accounts.stability_fee_vault.to_account_info(),
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
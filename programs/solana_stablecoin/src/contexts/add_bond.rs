@@ -18,8 +18,9 @@ pub struct AddSupportedBond<'info> {
 
     #[account(
         mut,
-        seeds = [FACTORY_STATE_SEED],
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
         bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::states::{stablecoin::StablecoinState, minter_allowlist::MinterAllowlistEntry};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// AddMinter approves a minter for an allowlist-only stablecoin.
+/// Only the stablecoin authority manages the allowlist.
+#[derive(Accounts)]
+#[instruction(minter: Pubkey)]
+pub struct AddMinter<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_state.authority == authority.key() @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MinterAllowlistEntry::INIT_SPACE,
+        seeds = [MINTER_ALLOWLIST_SEED, stablecoin_state.key().as_ref(), minter.as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, MinterAllowlistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_minter(
+    ctx: Context<AddMinter>,
+    minter: Pubkey,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let entry = &mut ctx.accounts.allowlist_entry;
+    entry.stablecoin = ctx.accounts.stablecoin_state.key();
+    entry.minter = minter;
+    entry.added_by = ctx.accounts.authority.key();
+    entry.added_at = timestamp;
+    entry.bump = ctx.bumps.allowlist_entry;
+
+    emit!(MinterAdded {
+        stablecoin: ctx.accounts.stablecoin_state.key(),
+        minter,
+        authority: ctx.accounts.authority.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
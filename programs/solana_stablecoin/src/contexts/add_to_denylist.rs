@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::states::{stablecoin::StablecoinState, denylist::DenylistEntry};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// AddToDenylist bars an address from minting a compliance-mode stablecoin
+/// and from every exit: burning, redemption, collateral withdrawal and
+/// settlement. Gated by the authority or its compliance officer.
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct AddToDenylist<'info> {
+    #[account(mut)]
+    pub officer: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_state.can_enforce_compliance(&officer.key()) @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.compliance_mode @ StablecoinError::ComplianceModeDisabled,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        init,
+        payer = officer,
+        space = 8 + DenylistEntry::INIT_SPACE,
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), address.as_ref()],
+        bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_to_denylist(
+    ctx: Context<AddToDenylist>,
+    address: Pubkey,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let entry = &mut ctx.accounts.denylist_entry;
+    entry.stablecoin = ctx.accounts.stablecoin_state.key();
+    entry.address = address;
+    entry.added_by = ctx.accounts.officer.key();
+    entry.added_at = timestamp;
+    entry.bump = ctx.bumps.denylist_entry;
+
+    emit!(AddressDenylisted {
+        stablecoin: ctx.accounts.stablecoin_state.key(),
+        address,
+        by: ctx.accounts.officer.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::states::{factory_state::FactoryState, kyc_attestation::KycAttestation};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::kyc::is_valid_jurisdiction;
+
+/// AttestKyc records or updates a holder's KYC tier and jurisdiction for the
+/// factory's stablecoins. Attesting with an expiry in the past revokes it.
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AttestKyc<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + KycAttestation::INIT_SPACE,
+        seeds = [KYC_ATTESTATION_SEED, factory_state.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn attest_kyc(
+    ctx: Context<AttestKyc>,
+    user: Pubkey,
+    tier: u8,
+    jurisdiction: [u8; 2],
+    expires_at: i64,
+) -> Result<()> {
+    require!(is_valid_jurisdiction(&jurisdiction), StablecoinError::InvalidJurisdiction);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let attestation = &mut ctx.accounts.kyc_attestation;
+    attestation.factory = ctx.accounts.factory_state.key();
+    attestation.user = user;
+    attestation.tier = tier;
+    attestation.jurisdiction = jurisdiction;
+    attestation.expires_at = expires_at;
+    attestation.attested_by = ctx.accounts.admin.key();
+    attestation.attested_at = timestamp;
+    attestation.bump = ctx.bumps.kyc_attestation;
+
+    emit!(KycAttested {
+        factory: ctx.accounts.factory_state.key(),
+        user,
+        tier,
+        jurisdiction,
+        expires_at,
+        admin: ctx.accounts.admin.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
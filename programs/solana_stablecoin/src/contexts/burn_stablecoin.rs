@@ -1,13 +1,16 @@
 use anchor_lang::{prelude::*, system_program};
-use stablebond_sdk::find_kyc_pda;
 use switchboard_solana::AggregatorAccountData;
-use crate::{states::{factory_state::FactoryState, stablecoin::StablecoinState}, sol_fee_vault::SolFeeVault, user::UserState};
+use crate::{states::{factory_state::FactoryState, kyc_attestation::KycAttestation, stablecoin::StablecoinState, flow_limits::{record_flow, FlowDirection}}, sol_fee_vault::SolFeeVault, user::UserState};
 use anchor_spl::{
-    associated_token::AssociatedToken, token::{self, Mint, Token, TokenAccount}
+    associated_token::AssociatedToken, token::{self, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
 };
 use crate::errors::StablecoinError;
 use crate::constants::*;
 use crate::events::*;
+use crate::compliance::verify_holder;
+use crate::oracle::load_bond_price;
+use crate::contexts::accrue_stability_fee::collect_stability_fee;
 
 #[derive(Accounts)]
 pub struct BurnStablecoin<'info> {
@@ -16,14 +19,18 @@ pub struct BurnStablecoin<'info> {
 
     #[account(
         mut,
-        constraint = !stablecoin_state.is_paused @ StablecoinError::StablecoinPaused
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
     )]
     pub stablecoin_state: Box<Account<'info, StablecoinState>>,
 
     #[account(
         mut,
-        seeds = [FACTORY_STATE_SEED],
-        bump
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
@@ -52,15 +59,16 @@ pub struct BurnStablecoin<'info> {
         mut,
         associated_token::mint = stablecoin_mint,
         associated_token::authority = user_state,
+        associated_token::token_program = stablecoin_token_program,
     )]
-    pub user_stablecoin_account: Account<'info, TokenAccount>,
+    pub user_stablecoin_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
     /// The stablecoin mint
     #[account(
         mut,
         address = stablecoin_state.mint
     )]
-    pub stablecoin_mint: Box<Account<'info, Mint>>,
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     /// Collateral vault
     #[account(
@@ -71,19 +79,48 @@ pub struct BurnStablecoin<'info> {
 
     #[account(
         mut,
-        seeds = [SOL_FEE_VAULT_SEED],
+        seeds = [SOL_FEE_VAULT_SEED, factory_state.id_seed().as_ref()],
         bump = sol_fee_vault.bump,
     )]
     pub sol_fee_vault: Account<'info, SolFeeVault>,
 
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
     /// CHECK: Verified in logic
     pub kyc_info: AccountInfo<'info>,
 
+    /// Holder's tier and jurisdiction, needed when the stablecoin sets KYC requirements
+    #[account(
+        seeds = [KYC_ATTESTATION_SEED, factory_state.key().as_ref(), user.key().as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// CHECK: The user's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
     // Switchboard oracle accounts
     pub oracle: AccountLoader<'info, AggregatorAccountData>,
 
+    /// FX feed into the target currency, required when the stablecoin has one
+    pub fx_oracle: Option<AccountLoader<'info, AggregatorAccountData>>,
+
     // Programs
     pub token_program: Program<'info, Token>,
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -93,125 +130,33 @@ impl<'info> BurnStablecoin<'info> {
     pub fn validate(&self, amount: u64) -> Result<()> {
         msg!("Starting validation for burn amount: {}", amount);
 
-        // 1. Verify KYC using Etherfuse
-        let (kyc_pda, _) = find_kyc_pda(self.user.key());
-        require!(
-            self.kyc_info.key() == kyc_pda,
-            StablecoinError::InvalidKycAccount
-        );
-
-        // Verify the account exists and is owned by Etherfuse program        
-        require!(
-            self.kyc_info.owner == &stablebond_sdk::ID,
-            StablecoinError::InvalidKycAccount
-        );
-
-        // 2. Check minimum redemption
-        let bond_config = self.factory_state.get_bond_config(
-            &self.stablecoin_state.bond_mint
-        ).ok_or(StablecoinError::BondNotFound)?;
-
-        require!(
-            amount >= bond_config.min_redemption_amount,
-            StablecoinError::RedeemAmountTooSmall
-        );
-
-        // 3. Verify oracle staleness
-        let clock = Clock::get()?;
-        require!(
-            clock.unix_timestamp - self.stablecoin_state.last_price_update 
-                <= ORACLE_STALENESS_THRESHOLD,
-            StablecoinError::StaleOraclePrice
-        );
+        // KYC and denylist, shared with quote_burn
+        verify_holder(
+            &self.stablecoin_state,
+            &self.user.key(),
+            &self.kyc_info,
+            self.kyc_attestation.as_deref(),
+            &self.denylist_entry,
+        )?;
 
-        // 4. Verify user has enough stablecoins
+        // Verify user has enough stablecoins
         require!(
             self.user_stablecoin_account.amount >= amount,
             StablecoinError::InsufficientStablecoinBalance
         );
 
-        // 5. Verify vault has enough bonds
-        require!(
-            self.collateral_vault.amount >= self.calculate_bond_return(
-                amount,
-                self.get_oracle_price()?,
-                self.stablecoin_state.collateral_ratio,
-            )?,
-            StablecoinError::InsufficientCollateral
-        );
-
         Ok(())
     }
     
     pub fn get_oracle_price(&self) -> Result<u64> {
         msg!("Fetching oracle price");
-        
-        let feed = self.oracle.load()?;
-        // Convert to absolute value since prices can't be negative
-        let mantissa = feed.latest_confirmed_round.result.mantissa.abs() as u128;
-        
-        let price = mantissa
-            .checked_mul(PRICE_SCALE)
-            .ok_or(StablecoinError::MathOverflow)?;
-    
-        require!(price > 0, StablecoinError::InvalidOraclePrice);
-        
-        Ok(price as u64)
-    }
-
-    pub fn calculate_bond_return(
-        &self,
-        stablecoin_amount: u64,
-        bond_price: u64,    // Price scaled by PRICE_SCALE
-        collateral_ratio: u16,
-    ) -> Result<u64> {
-        msg!("Calculating bond return for {} stablecoins at price {}", 
-            stablecoin_amount, bond_price);
-
-        // Calculate bond amount to return
-        // bond_amount = (stablecoin_amount * collateral_ratio * PRICE_SCALE) / (bond_price * BPS_SCALE)
-        
-        // First multiply stablecoin amount by collateral ratio
-        let collateral_needed = (stablecoin_amount as u128)
-            .checked_mul(collateral_ratio as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-
-        // Multiply by PRICE_SCALE to match bond price scaling
-        let scaled_collateral = collateral_needed
-            .checked_mul(PRICE_SCALE as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-
-        // Divide by bond price and BPS_SCALE
-        let bond_amount = scaled_collateral
-            .checked_div(bond_price as u128)
-            .ok_or(StablecoinError::MathOverflow)?
-            .checked_div(BPS_SCALE as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-
-        require!(
-            bond_amount <= u64::MAX as u128,
-            StablecoinError::ExcessivePriceDeviation
-        );
 
-        Ok(bond_amount as u64)
-    }
-
-    pub fn calculate_fee_amount(&self, amount: u64) -> Result<u64> {
-        let fee_rate = self.factory_state
-            .get_fee_rate(&self.stablecoin_state.bond_mint)?;
-            
-        let fee_amount = (amount as u128)
-            .checked_mul(fee_rate as u128)
-            .ok_or(StablecoinError::MathOverflow)?
-            .checked_div(BPS_SCALE as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-    
-        require!(
-            fee_amount <= u64::MAX as u128,
-            StablecoinError::FeeTooLarge
-        );
-            
-        Ok(fee_amount as u64)
+        load_bond_price(
+            &self.oracle,
+            &self.stablecoin_state,
+            &self.fx_oracle,
+            self.stablecoin_state.oracle_staleness_threshold(&self.factory_state),
+        )
     }
 
     pub fn collect_fees(
@@ -230,7 +175,7 @@ impl<'info> BurnStablecoin<'info> {
                     to: ctx.accounts.sol_fee_vault.to_account_info(),
                 }
             ),
-            fee_amount as u64
+            fee_amount
         )?;
     
         Ok(())
@@ -284,35 +229,58 @@ impl<'info> BurnStablecoin<'info> {
 pub fn burn_stablecoin(
     ctx: Context<BurnStablecoin>,
     stablecoin_amount: u64,
+    min_bonds_out: u64,
+    max_fee: u64,
+    expires_at: Option<i64>,
 ) -> Result<()> {
     msg!("Starting stablecoin burn process");
 
     // 1. Validate all conditions
+    if let Some(expires_at) = expires_at {
+        require!(
+            Clock::get()?.unix_timestamp <= expires_at,
+            StablecoinError::TransactionExpired
+        );
+    }
+
+    // Bring every position's stability fee up to date so the burn repays it
+    // along with the principal
+    collect_stability_fee(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        ctx.accounts.stability_fee_vault.to_account_info(),
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+    )?;
+
     ctx.accounts.validate(stablecoin_amount)?;
 
     // 2. Get oracle price
     let bond_price = ctx.accounts.get_oracle_price()?;
     msg!("Current bond price (scaled): {}", bond_price);
 
-    // 3. Calculate bond amount to return
-    // This is reverse of mint calculation:
-    // bond_amount = (stablecoin_amount * collateral_ratio * PRICE_SCALE) / (bond_price * BPS_SCALE)
-    let bond_amount = ctx.accounts.calculate_bond_return(
-        stablecoin_amount,
-        bond_price,
-        ctx.accounts.stablecoin_state.collateral_ratio,
-    )?;
+    // 3. Calculate bonds returned from the user's position and the fee
+    // bond_amount = share.bond_amount * stablecoin_amount / share.mint_amount
+    let (bond_amount, fee_amount) = ctx.accounts.stablecoin_state
+        .price_burn(&ctx.accounts.factory_state, &ctx.accounts.user.key(), stablecoin_amount)?;
     msg!("Calculated bond return amount: {}", bond_amount);
 
-    // 4. Calculate and collect redemption fees
-    let fee_amount = ctx.accounts.calculate_fee_amount(stablecoin_amount)?;
+    require!(
+        ctx.accounts.collateral_vault.amount >= bond_amount,
+        StablecoinError::InsufficientCollateral
+    );
+
+    // Protect the caller from price moves since they quoted
+    require!(bond_amount >= min_bonds_out, StablecoinError::ExcessiveSlippage);
+    require!(fee_amount <= max_fee, StablecoinError::FeeExceedsMaximum);
+
+    // 4. Collect fees
     ctx.accounts.collect_fees(&ctx, fee_amount)?;
 
     // 5. Burn stablecoins from user
-    token::burn(
+    token_interface::burn(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::Burn {
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::Burn {
                 mint: ctx.accounts.stablecoin_mint.to_account_info(),
                 from: ctx.accounts.user_stablecoin_account.to_account_info(),
                 authority: ctx.accounts.user_state.to_account_info(),
@@ -353,6 +321,16 @@ pub fn burn_stablecoin(
         stablecoin_amount,
     )?;
 
+    // Count the burn against the stablecoin's and the holder's flow limits
+    let user_state = &mut ctx.accounts.user_state;
+    record_flow(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.user.key(),
+        &mut user_state.flow_window,
+        FlowDirection::Redeem,
+        stablecoin_amount,
+    )?;
+
     // 8. Update state
     let stablecoin = &mut ctx.accounts.stablecoin_state;
     stablecoin.total_supply = stablecoin.total_supply
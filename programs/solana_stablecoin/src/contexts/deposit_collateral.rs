@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use crate::{states::{factory_state::FactoryState, stablecoin::StablecoinState}, user::UserState};
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// DepositCollateral tops up an existing position with stablebonds without minting.
+/// This lets holders raise their collateral ratio during price swings.
+#[derive(Accounts)]
+pub struct DepositCollateral<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            USER_STATE_SEED,
+            user.key().as_ref(),
+            stablecoin_state.mint.as_ref()
+        ],
+        bump = user_state.bump
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    /// User's stablebond token account to deposit from
+    #[account(
+        mut,
+        constraint = user_bond_account.owner == user.key() @ StablecoinError::InvalidTokenAccountOwner,
+        constraint = user_bond_account.mint == stablecoin_state.bond_mint @ StablecoinError::InvalidBondMint
+    )]
+    pub user_bond_account: Account<'info, TokenAccount>,
+
+    /// Collateral vault
+    #[account(
+        mut,
+        address = stablecoin_state.collateral_vault
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DepositCollateral<'info> {
+    pub fn validate(&self, bond_amount: u64) -> Result<()> {
+        msg!("Starting validation for collateral deposit: {}", bond_amount);
+
+        require!(bond_amount > 0, StablecoinError::DepositTooSmall);
+
+        // Top-ups only apply to an existing position
+        require!(
+            self.stablecoin_state.user_shares.iter()
+                .any(|share| share.owner == self.user.key()),
+            StablecoinError::NoUserPosition
+        );
+
+        require!(
+            self.user_bond_account.amount >= bond_amount,
+            StablecoinError::InsufficientCollateral
+        );
+
+        Ok(())
+    }
+}
+
+pub fn deposit_collateral(
+    ctx: Context<DepositCollateral>,
+    bond_amount: u64,
+) -> Result<()> {
+    // 1. Validate position and balance
+    ctx.accounts.validate(bond_amount)?;
+
+    // 2. Transfer bonds to vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.user_bond_account.to_account_info(),
+                to: ctx.accounts.collateral_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            }
+        ),
+        bond_amount
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let user_key = ctx.accounts.user.key();
+
+    // 3. Update the user's position
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let share = stablecoin.user_shares
+        .iter_mut()
+        .find(|s| s.owner == user_key)
+        .ok_or(StablecoinError::UserShareNotFound)?;
+
+    share.bond_amount = share.bond_amount
+        .checked_add(bond_amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+    share.timestamp = timestamp;
+    let position_collateral = share.bond_amount;
+
+    // 4. Update state
+    stablecoin.total_collateral = stablecoin.total_collateral
+        .checked_add(bond_amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+    stablecoin.last_updated = timestamp;
+
+    emit!(CollateralDeposited {
+        user: user_key,
+        stablecoin: stablecoin.key(),
+        bond_amount,
+        position_collateral,
+        timestamp,
+    });
+
+    Ok(())
+}
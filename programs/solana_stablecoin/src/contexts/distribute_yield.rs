@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
-use stablebond_sdk::types::PaymentFeedType;
 use crate::{states::{factory_state::FactoryState, stablecoin::StablecoinState}, user::UserState};
 use anchor_spl::{
-    associated_token::AssociatedToken, token::{self, Mint, Token, TokenAccount}
+    associated_token::AssociatedToken, token::Mint,
+    token_interface::{self, TokenInterface},
 };
 use crate::errors::StablecoinError;
 use switchboard_solana::AggregatorAccountData;
 use crate::constants::*;
 use crate::events::*;
+use crate::oracle::load_yield_price;
 
 #[derive(Accounts)]
 pub struct DistributeYield<'info> {
@@ -16,16 +17,19 @@ pub struct DistributeYield<'info> {
 
     #[account(
         mut,
-        seeds = [FACTORY_STATE_SEED],
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
         bump,
         constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused,
-        constraint = factory_state.is_authorized_collector(distributor.key()) @ StablecoinError::Unauthorized
+        constraint = factory_state.is_authorized_collector(distributor.key()) @ StablecoinError::Unauthorized,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
     #[account(
         mut,
-        constraint = !stablecoin_state.is_paused @ StablecoinError::StablecoinPaused
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
     )]
     pub stablecoin_state: Box<Account<'info, StablecoinState>>,
 
@@ -54,14 +58,15 @@ pub struct DistributeYield<'info> {
     #[account(
         mut,
         associated_token::mint = yield_mint,
-        associated_token::authority = factory_state
+        associated_token::authority = factory_state,
+        associated_token::token_program = token_program,
     )]
-    pub usdc_fee_vault: Account<'info, TokenAccount>,
+    pub usdc_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
     #[account(
-        address = USDC_MINT @ StablecoinError::InvalidUSDCMint
+        address = factory_state.fee_mint @ StablecoinError::InvalidFeeMint
     )]
-    pub yield_mint: Box<Account<'info, Mint>>,
+    pub yield_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     /// User's yield token account
     #[account(
@@ -69,10 +74,11 @@ pub struct DistributeYield<'info> {
         payer = distributor,
         associated_token::mint = yield_mint,
         associated_token::authority = user_state,
+        associated_token::token_program = token_program,
     )]
-    pub user_yield_account: Account<'info, TokenAccount>,
+    pub user_yield_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -99,7 +105,7 @@ impl<'info> DistributeYield<'info> {
         // Verify rebase interval has passed
         let current_time = Clock::get()?.unix_timestamp;
         require!(
-            current_time - self.stablecoin_state.last_rebase >= REBASE_INTERVAL,
+            current_time - self.stablecoin_state.last_rebase >= self.factory_state.protocol_params.rebase_interval,
             StablecoinError::RebaseTooEarly 
         );
 
@@ -111,112 +117,34 @@ impl<'info> DistributeYield<'info> {
         );
 
         require!(
-            current_time - self.stablecoin_state.last_rate_update <= RATE_UPDATE_INTERVAL,
+            current_time - self.stablecoin_state.last_rate_update <= self.factory_state.protocol_params.rate_update_interval,
             StablecoinError::StaleError
         );
 
         Ok(())
     }
 
-    pub fn get_conversion_rate(&self, feed_type: &PaymentFeedType) -> Result<u128> {
-        let current_time = Clock::get()?.unix_timestamp;
-        let feed = self.oracle.load()?;
-
-        // Verify feed freshness
-        require!(
-            current_time - feed.latest_confirmed_round.round_open_timestamp <= RATE_FRESHNESS_THRESHOLD,
-            StablecoinError::StaleError
-        );
-
-        match feed_type {
-            // USD pairs 
-            PaymentFeedType::UsdcMxn | 
-            PaymentFeedType::SwitchboardUsdcMxn | 
-            PaymentFeedType::SwitchboardUsdcBrl | 
-            PaymentFeedType::SwitchboardUsdcEur | 
-            PaymentFeedType::SwitchboardUsdcGbp => {
-                msg!("Using oracle conversion rate for {:?}", feed_type);
-                Ok(feed.latest_confirmed_round.result.mantissa.abs() as u128)
-            },
-
-            // USD/USD pairs
-            PaymentFeedType::UsdcUsd |
-            PaymentFeedType::SwitchboardUsdcUsd => {
-                msg!("Using 1:1 USD conversion");
-                Ok(PRICE_SCALE)
-            },
-
-            PaymentFeedType::Stub => {
-                msg!("Using stub conversion rate");
-                Ok(PRICE_SCALE)
-            }
-        }
-    }
-
     pub fn calculate_rebase_yield(&self) -> Result<(u64, u64)> {
         let bond = stablebond_sdk::accounts::Bond::try_from_slice(
             &self.bond_info.try_borrow_data()?
         )?;
 
-        // Get current price and conversion rate
-        let base_price = self.oracle.load()?.latest_confirmed_round.result.mantissa.abs() as u128;
-        let conversion_rate = self.get_conversion_rate(&bond.payment_feed_type)?;
-
-        let current_price = base_price
-        .checked_mul(conversion_rate)
-        .ok_or(StablecoinError::MathOverflow)?
-        .checked_div(PRICE_SCALE)
-        .ok_or(StablecoinError::MathOverflow)?;
-
-        self.calculate_yield_from_price(current_price)
-    }
-
-    fn calculate_yield_from_price(&self, current_price: u128) -> Result<(u64, u64)> {
-        // Calculate time elapsed since last rebase
-        let current_time = Clock::get()?.unix_timestamp;
-        let time_elapsed = (current_time - self.stablecoin_state.last_rebase) as u128;
-
-        let user_share = self.stablecoin_state.user_shares
-            .iter()
-            .find(|share| share.owner == self.user_state.user)
-            .ok_or(StablecoinError::NoUserPosition)?;
-
-        // Calculate yield based on price and share
-        let base_amount = user_share.mint_amount as u128;
-        
-        let yield_rate = current_price
-            .checked_mul(time_elapsed)
-            .ok_or(StablecoinError::MathOverflow)?
-            .checked_div(YEAR_IN_SECONDS as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-
-        let total_yield = base_amount
-            .checked_mul(yield_rate)
-            .ok_or(StablecoinError::MathOverflow)?
-            .checked_div(PRICE_SCALE as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-
-        let protocol_fee = total_yield
-            .checked_mul(PROTOCOL_FEE_BPS as u128)
-            .ok_or(StablecoinError::MathOverflow)?
-            .checked_div(BPS_SCALE as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-
-        let user_yield = total_yield
-            .checked_sub(protocol_fee)
-            .ok_or(StablecoinError::MathOverflow)?;
-
-        require!(
-            protocol_fee <= u64::MAX as u128 && user_yield <= u64::MAX as u128,
-            StablecoinError::MathOverflow
-        );
+        // Get current price adjusted by the payment feed's conversion rate
+        let current_price = load_yield_price(
+            &self.oracle,
+            &bond.payment_feed_type,
+            self.factory_state.protocol_params.rate_freshness_threshold,
+        )?;
 
-        Ok((protocol_fee as u64, user_yield as u64))
+        self.stablecoin_state.rebase_yield(
+            &self.factory_state,
+            &self.user_state.user,
+            current_price,
+            Clock::get()?.unix_timestamp,
+        )
     }
 }
 
-
-
 pub fn distribute_yield(ctx: Context<DistributeYield>) -> Result<()> {
     // 1. Validate state and check rebase interval
     ctx.accounts.validate()?;
@@ -226,10 +154,10 @@ pub fn distribute_yield(ctx: Context<DistributeYield>) -> Result<()> {
 
     // 3. Transfer protocol fee
     if protocol_fee > 0 {
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                token::MintTo {
+                token_interface::MintTo {
                     mint: ctx.accounts.yield_mint.to_account_info(),
                     to: ctx.accounts.usdc_fee_vault.to_account_info(),
                     authority: ctx.accounts.stablecoin_state.to_account_info(),
@@ -247,10 +175,10 @@ pub fn distribute_yield(ctx: Context<DistributeYield>) -> Result<()> {
 
     // 4. Mint user yield
     if user_yield > 0 {
-        token::mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                token::MintTo {
+                token_interface::MintTo {
                     mint: ctx.accounts.yield_mint.to_account_info(),
                     to: ctx.accounts.user_yield_account.to_account_info(),
                     authority: ctx.accounts.stablecoin_state.to_account_info(),
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::states::stablecoin::StablecoinState;
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// FreezeHolder freezes a holder's stablecoin token account. Requires compliance
+/// mode, where the stablecoin PDA is the mint's freeze authority.
+#[derive(Accounts)]
+pub struct FreezeHolder<'info> {
+    pub officer: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_state.can_enforce_compliance(&officer.key()) @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.compliance_mode @ StablecoinError::ComplianceModeDisabled,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(address = stablecoin_state.mint)]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = stablecoin_mint,
+        token::token_program = stablecoin_token_program,
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn freeze_holder(ctx: Context<FreezeHolder>) -> Result<()> {
+    token_interface::freeze_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::FreezeAccount {
+                account: ctx.accounts.holder_token_account.to_account_info(),
+                mint: ctx.accounts.stablecoin_mint.to_account_info(),
+                authority: ctx.accounts.stablecoin_state.to_account_info(),
+            },
+            &[&[
+                STABLECOIN_SEED,
+                ctx.accounts.stablecoin_state.creator.as_ref(),
+                ctx.accounts.stablecoin_state.original_symbol.as_bytes(),
+                &[ctx.accounts.stablecoin_state.bump],
+            ]]
+        )
+    )?;
+
+    emit!(HolderFrozen {
+        stablecoin: ctx.accounts.stablecoin_state.key(),
+        holder: ctx.accounts.holder_token_account.owner,
+        token_account: ctx.accounts.holder_token_account.key(),
+        by: ctx.accounts.officer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
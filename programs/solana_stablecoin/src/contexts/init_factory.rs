@@ -4,7 +4,7 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
     associated_token::AssociatedToken,
 };
-use crate::states::{factory_state::FactoryState, sol_fee_vault::SolFeeVault};
+use crate::states::{factory_state::{factory_id_seed, FactoryState}, sol_fee_vault::SolFeeVault, protocol_params::ProtocolParams};
 use crate::errors::StablecoinError;
 use crate::events::FactoryInitialized;
 use crate::constants::*;
@@ -13,6 +13,7 @@ use crate::constants::*;
 /// This is the first instruction that must be called to set up the protocol.
 /// The admin will have control over protocol parameters and emergency functions.
 #[derive(Accounts)]
+#[instruction(factory_id: u64)]
 pub struct InitializeFactory<'info> {
     /// The admin who will have authority over the factory
     /// This account must be a signer as they're establishing control of the protocol
@@ -20,29 +21,25 @@ pub struct InitializeFactory<'info> {
     pub admin: Signer<'info>,
 
     /// The factory state PDA that stores all protocol configurations
-    /// Seeds: ["factory_state"]
+    /// Seeds: ["factory_state", factory_id]
     /// This account is initialized here and will store all factory-wide parameters
     #[account(
         init,
         payer = admin,
         space = 8 + FactoryState::INIT_SPACE,
-        seeds = [FACTORY_STATE_SEED],
-        bump,
-        constraint = factory_state.to_account_info().data_is_empty() 
-        @ StablecoinError::FactoryAlreadyInitialized
+        seeds = [FACTORY_STATE_SEED, factory_id_seed(factory_id).as_ref()],
+        bump
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
-    #[account(
-        address = USDC_MINT @ StablecoinError::InvalidUSDCMint
-    )]
+    /// Fee and yield mint for the factory, pinned to USDC on mainnet builds
     pub fee_mint: Account<'info, Mint>,
 
     #[account(
         init,
         payer = admin,
         space = 8 + SolFeeVault::INIT_SPACE,
-        seeds = [SOL_FEE_VAULT_SEED],
+        seeds = [SOL_FEE_VAULT_SEED, factory_id_seed(factory_id).as_ref()],
         bump
     )]
     pub sol_fee_vault: Box<Account<'info, SolFeeVault>>,
@@ -81,6 +78,13 @@ impl<'info> InitializeFactory<'info> {
             StablecoinError::InvalidFeeRate
         );
 
+        #[cfg(feature = "mainnet")]
+        require_keys_eq!(
+            self.fee_mint.key(),
+            USDC_MINT,
+            StablecoinError::InvalidUSDCMint
+        );
+
         Ok(())
     }
 }
@@ -88,6 +92,7 @@ impl<'info> InitializeFactory<'info> {
 
 pub fn initialize_factory(
     ctx: Context<InitializeFactory>,
+    factory_id: u64,
     min_collateral_ratio: u16,
     base_fee_rate: u16,
 ) -> Result<()> {
@@ -104,15 +109,19 @@ pub fn initialize_factory(
     factory_state.authorized_collectors = Vec::with_capacity(MAX_ALLOWED_COLLECTORS);
     
     // Initialize with default values where appropriate
+    factory_state.factory_id = factory_id;
     factory_state.admin = admin_key;
+    factory_state.guardian = admin_key;
     factory_state.authorized_collectors.push(admin_key); 
     factory_state.fee_vault = ctx.accounts.fee_vault.key();
+    factory_state.fee_mint = ctx.accounts.fee_mint.key();
     factory_state.is_paused = false;
     factory_state.min_collateral_ratio = min_collateral_ratio;
     factory_state.base_fee_rate = base_fee_rate;
     factory_state.stablecoin_count = 0;
+    factory_state.protocol_params = ProtocolParams::default();
     factory_state.last_update = Clock::get()?.unix_timestamp;
-    factory_state.protocol_version = 1;
+    factory_state.protocol_version = FACTORY_SCHEMA_VERSION;
     factory_state.bump = ctx.bumps.factory_state;
     factory_state.reserved = [0; RESERVE_SPACE];
 
@@ -123,8 +132,11 @@ pub fn initialize_factory(
     sol_fee_vault.bump = ctx.bumps.sol_fee_vault;
 
     emit!(FactoryInitialized {
+        factory: factory_state.key(),
+        factory_id,
         admin: ctx.accounts.admin.key(),
         fee_vault: ctx.accounts.fee_vault.key(),
+        fee_mint: ctx.accounts.fee_mint.key(),
         min_collateral_ratio,
         base_fee_rate,
         protocol_version: factory_state.protocol_version,
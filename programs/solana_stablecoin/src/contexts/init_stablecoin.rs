@@ -1,19 +1,31 @@
 // contexts/create_stablecoin.rs
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
-    token::{Mint, Token, TokenAccount},
+    token::{self, Mint, Token, TokenAccount},
+    token_2022,
+    token_interface::{self, ExtensionsVec, TokenInterface},
+    token_2022::spl_token_2022::extension::ExtensionType,
+    metadata::{self, Metadata, mpl_token_metadata::{self, types::DataV2}},
     associated_token::AssociatedToken,
 };
-use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState, user::UserState};
+use stablebond_sdk::accounts::Bond;
+use switchboard_solana::AggregatorAccountData;
+use crate::states::{factory_state::FactoryState, stablecoin::{KycPolicy, KycRequirements, MintPolicy, StablecoinState}, user::UserState, token_config::Token2022Config, symbol_registry::SymbolRecord, stablecoin_index::StablecoinIndex, fx_feed::FxFeed, protocol_params::StablecoinParamOverrides, flow_limits::{FlowLimits, FlowWindow}};
 use crate::errors::StablecoinError;
 use crate::constants::*;
 use crate::events::*;
+use crate::oracle::{is_supported_currency, payment_feed_currency};
 
 #[derive(Accounts)]
 #[instruction(
     name: String,
     symbol: String,
     target_currency: String,
+    uri: String,
+    collateral_ratio: u16,
+    fee_rate: u16,
+    token_2022_config: Option<Token2022Config>,
+    compliance_mode: bool,
 )]
 pub struct CreateStablecoin<'info> {
     #[account(mut)]
@@ -22,9 +34,10 @@ pub struct CreateStablecoin<'info> {
     /// Factory state PDA
     #[account(
         mut,
-        seeds = [FACTORY_STATE_SEED],
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
         bump = factory_state.bump,
-        constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused
+        constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
@@ -42,6 +55,30 @@ pub struct CreateStablecoin<'info> {
     )]
     pub stablecoin_state: Box<Account<'info, StablecoinState>>,
 
+    /// Factory-wide claim on the symbol; fails if taken or reserved
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + SymbolRecord::INIT_SPACE,
+        seeds = [SYMBOL_REGISTRY_SEED, factory_state.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_record: Box<Account<'info, SymbolRecord>>,
+
+    /// Enumeration entry at the factory's current stablecoin count
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + StablecoinIndex::INIT_SPACE,
+        seeds = [
+            STABLECOIN_INDEX_SEED,
+            factory_state.key().as_ref(),
+            factory_state.stablecoin_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub stablecoin_index: Box<Account<'info, StablecoinIndex>>,
+
     #[account(
         init,
         payer = creator,
@@ -55,18 +92,14 @@ pub struct CreateStablecoin<'info> {
     )]
     pub creator_state: Box<Account<'info, UserState>>,
 
-    /// The mint for the stablecoin
-    #[account(
-        init,
-        payer = creator,
-        mint::decimals = STABLECOIN_DECIMALS,
-        mint::authority = stablecoin_state,
-    )]
-    pub mint: Box<Account<'info, Mint>>,
+    /// The mint for the stablecoin, created in instruction logic under
+    /// either SPL Token or Token-2022 depending on the requested extensions
+    #[account(mut)]
+    pub mint: Signer<'info>,
 
     /// Yield mint
     #[account(
-        address = USDC_MINT @ StablecoinError::InvalidUSDCMint
+        address = factory_state.fee_mint @ StablecoinError::InvalidFeeMint
     )]
     pub yield_mint: Box<Account<'info, Mint>>,
 
@@ -85,23 +118,73 @@ pub struct CreateStablecoin<'info> {
     /// CHECK: Validated in is_bond_supported using Etherfuse PDA
     pub bond_info: AccountInfo<'info>,
 
+    /// Switchboard FX feed converting the bond's payment currency into the
+    /// target currency. Required only when the two currencies differ.
+    pub fx_oracle: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// The factory's registered feed for the currency pair, which fx_oracle must match
+    pub fx_feed: Option<Account<'info, FxFeed>>,
+
+    /// Metaplex metadata PDA for the stablecoin mint, created via CPI
+    /// CHECK: Seeds are verified against the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
     // Required programs
     pub token_program: Program<'info, Token>,
+    /// Token program that will own the stablecoin mint
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> CreateStablecoin<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn validate(
         &self,
         name: &str,
         symbol: &str,
-        _target_currency: &str,
+        target_currency: &str,
+        uri: &str,
+        collateral_ratio: u16,
+        fee_rate: u16,
+        stability_fee_bps: u16,
     ) -> Result<()> {
         // Basic validation
         require!(!name.is_empty() && name.len() <= 32, StablecoinError::InvalidName);
-        require!(!symbol.is_empty() && symbol.len() <= 10, StablecoinError::InvalidSymbol);
+        SymbolRecord::validate_symbol(symbol)?;
+        require!(uri.len() <= MAX_URI_LENGTH, StablecoinError::InvalidUri);
+
+        // Collateral ratio must respect the factory floor and protocol ceiling
+        require!(
+            collateral_ratio >= self.factory_state.min_collateral_ratio,
+            StablecoinError::CollateralRatioTooLow
+        );
+        require!(
+            collateral_ratio <= MAX_ALLOWED_COLLATERAL_RATIO,
+            StablecoinError::CollateralRatioTooHigh
+        );
+
+        require!(
+            fee_rate <= MAX_FEE_RATE_BPS,
+            StablecoinError::InvalidFeeRate
+        );
+
+        require!(
+            stability_fee_bps <= MAX_STABILITY_FEE_BPS,
+            StablecoinError::InvalidStabilityFee
+        );
 
         require!(
             self.factory_state.is_bond_supported(
@@ -111,22 +194,239 @@ impl<'info> CreateStablecoin<'info> {
             StablecoinError::UnsupportedBond
         );
 
+        // Target currency must be supported and priced through an FX feed
+        // whenever it differs from the bond's payment currency
+        require!(
+            is_supported_currency(target_currency),
+            StablecoinError::InvalidTargetCurrency
+        );
+
+        let bond = Bond::try_from_slice(&self.bond_info.try_borrow_data()?)?;
+        let bond_currency = payment_feed_currency(&bond.payment_feed_type);
+        msg!("Bond currency: {}, target currency: {}", bond_currency, target_currency);
+
+        if bond_currency != target_currency {
+            let fx_oracle = self.fx_oracle
+                .as_ref()
+                .ok_or(StablecoinError::MissingOracleAccount)?;
+            let fx_feed = self.fx_feed
+                .as_ref()
+                .ok_or(StablecoinError::MissingOracleAccount)?;
+
+            // Only the feed the admin registered for this pair can price the coin
+            require!(
+                fx_feed.factory == self.factory_state.key()
+                    && fx_feed.base_currency == bond_currency
+                    && fx_feed.quote_currency == target_currency,
+                StablecoinError::InvalidFxFeed
+            );
+            require!(
+                fx_oracle.key() == fx_feed.aggregator,
+                StablecoinError::InvalidFxFeed
+            );
+            fx_oracle.load()?;
+        } else {
+            require!(
+                self.fx_oracle.is_none() && self.fx_feed.is_none(),
+                StablecoinError::InvalidOracleConfig
+            );
+        }
+
         Ok(())
     }
+
+    /// Creates the stablecoin mint with the stablecoin PDA as mint authority, and
+    /// as freeze authority in compliance mode.
+    /// Token-2022 mints get a metadata pointer to the Metaplex metadata PDA plus
+    /// any requested permanent delegate, interest-bearing and transfer hook extensions.
+    pub fn create_mint(
+        &self,
+        token_2022_config: &Option<Token2022Config>,
+        compliance_mode: bool,
+    ) -> Result<()> {
+        let token_program_id = self.stablecoin_token_program.key();
+        let stablecoin_key = self.stablecoin_state.key();
+
+        let extensions: Option<ExtensionsVec> = match token_2022_config {
+            Some(config) => {
+                require!(token_program_id == token_2022::ID, StablecoinError::InvalidTokenProgram);
+
+                let mut extensions = vec![ExtensionType::MetadataPointer];
+                if config.permanent_delegate {
+                    extensions.push(ExtensionType::PermanentDelegate);
+                }
+                if config.interest_rate.is_some() {
+                    extensions.push(ExtensionType::InterestBearingConfig);
+                }
+                if config.transfer_hook_program.is_some() {
+                    extensions.push(ExtensionType::TransferHook);
+                }
+                Some(extensions)
+            }
+            None => {
+                require!(token_program_id == token::ID, StablecoinError::InvalidTokenProgram);
+                None
+            }
+        };
+
+        // 1. Allocate the mint account owned by the chosen token program
+        let space = token_interface::find_mint_account_size(extensions.as_ref())?;
+        system_program::create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: self.creator.to_account_info(),
+                    to: self.mint.to_account_info(),
+                }
+            ),
+            self.rent.minimum_balance(space),
+            space as u64,
+            &token_program_id,
+        )?;
+
+        // 2. Initialize extensions, which must precede the mint itself
+        if let Some(config) = token_2022_config {
+            let (metadata_pda, _) = mpl_token_metadata::accounts::Metadata::find_pda(&self.mint.key());
+            token_interface::metadata_pointer_initialize(
+                CpiContext::new(
+                    self.stablecoin_token_program.to_account_info(),
+                    token_interface::MetadataPointerInitialize {
+                        token_program_id: self.stablecoin_token_program.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                    }
+                ),
+                Some(stablecoin_key),
+                Some(metadata_pda),
+            )?;
+
+            if config.permanent_delegate {
+                token_interface::permanent_delegate_initialize(
+                    CpiContext::new(
+                        self.stablecoin_token_program.to_account_info(),
+                        token_interface::PermanentDelegateInitialize {
+                            token_program_id: self.stablecoin_token_program.to_account_info(),
+                            mint: self.mint.to_account_info(),
+                        }
+                    ),
+                    &stablecoin_key,
+                )?;
+            }
+
+            if let Some(rate) = config.interest_rate {
+                token_interface::interest_bearing_mint_initialize(
+                    CpiContext::new(
+                        self.stablecoin_token_program.to_account_info(),
+                        token_interface::InterestBearingMintInitialize {
+                            token_program_id: self.stablecoin_token_program.to_account_info(),
+                            mint: self.mint.to_account_info(),
+                        }
+                    ),
+                    Some(stablecoin_key),
+                    rate,
+                )?;
+            }
+
+            if let Some(hook_program) = config.transfer_hook_program {
+                token_interface::transfer_hook_initialize(
+                    CpiContext::new(
+                        self.stablecoin_token_program.to_account_info(),
+                        token_interface::TransferHookInitialize {
+                            token_program_id: self.stablecoin_token_program.to_account_info(),
+                            mint: self.mint.to_account_info(),
+                        }
+                    ),
+                    Some(stablecoin_key),
+                    Some(hook_program),
+                )?;
+            }
+        }
+
+        // 3. Initialize the mint with the stablecoin PDA as authority
+        let freeze_authority = compliance_mode.then_some(&stablecoin_key);
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                self.stablecoin_token_program.to_account_info(),
+                token_interface::InitializeMint2 {
+                    mint: self.mint.to_account_info(),
+                }
+            ),
+            STABLECOIN_DECIMALS,
+            &stablecoin_key,
+            freeze_authority,
+        )?;
+
+        Ok(())
+    }
+
+    /// Creates Metaplex metadata for the mint so wallets can display the coin.
+    /// The stablecoin PDA signs as both mint authority and update authority.
+    pub fn create_metadata(
+        &self,
+        name: &str,
+        symbol: &str,
+        uri: &str,
+        stablecoin_bump: u8,
+    ) -> Result<()> {
+        let creator_key = self.creator.key();
+        let signer_seeds: &[&[u8]] = &[
+            STABLECOIN_SEED,
+            creator_key.as_ref(),
+            symbol.as_bytes(),
+            &[stablecoin_bump],
+        ];
+
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                metadata::CreateMetadataAccountsV3 {
+                    metadata: self.metadata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    mint_authority: self.stablecoin_state.to_account_info(),
+                    payer: self.creator.to_account_info(),
+                    update_authority: self.stablecoin_state.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                &[signer_seeds]
+            ),
+            DataV2 {
+                name: name.to_string(),
+                symbol: symbol.to_string(),
+                uri: uri.to_string(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true,
+            true,
+            None,
+        )
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_stablecoin(
     ctx: Context<CreateStablecoin>,
     name: String,
     symbol: String,
     target_currency: String,
+    uri: String,
+    collateral_ratio: u16,
+    fee_rate: u16,
+    token_2022_config: Option<Token2022Config>,
+    compliance_mode: bool,
+    stability_fee_bps: u16,
 ) -> Result<()> {
 
     // bond_mint: The stablebond token mint (e.g., CETES, USTRY)
     // bond_info: The Etherfuse bond PDA
     // payment_feed_info: The Etherfuse payment feed PDA
     
-    ctx.accounts.validate(&name, &symbol, &target_currency)?;
+    ctx.accounts.validate(&name, &symbol, &target_currency, &uri, collateral_ratio, fee_rate, stability_fee_bps)?;
+    ctx.accounts.create_mint(&token_2022_config, compliance_mode)?;
+    ctx.accounts.create_metadata(&name, &symbol, &uri, ctx.bumps.stablecoin_state)?;
+
     let stablecoin = &mut ctx.accounts.stablecoin_state;
     
     // Initialize basic info
@@ -134,26 +434,76 @@ pub fn create_stablecoin(
     stablecoin.symbol = symbol.clone();
     stablecoin.original_symbol = symbol.clone();
     stablecoin.target_currency = target_currency.clone();
+    stablecoin.uri = uri.clone();
+    stablecoin.factory = ctx.accounts.factory_state.key();
     stablecoin.creator = ctx.accounts.creator.key();
+    stablecoin.authority = ctx.accounts.creator.key();
+    stablecoin.pending_authority = None;
+    stablecoin.metadata_editor = None;
+    stablecoin.pauser = None;
+    stablecoin.compliance_officer = None;
+    stablecoin.schema_version = STABLECOIN_SCHEMA_VERSION;
     stablecoin.mint = ctx.accounts.mint.key();
+    stablecoin.token_program = ctx.accounts.stablecoin_token_program.key();
     stablecoin.yield_mint = ctx.accounts.yield_mint.key();
     stablecoin.total_yield_collected = 0;
     stablecoin.collateral_vault = ctx.accounts.collateral_vault.key();
     stablecoin.bond_mint = ctx.accounts.bond_mint.key();
+    stablecoin.bond_scale = StablecoinState::decimals_scale(ctx.accounts.bond_mint.decimals)?;
+    stablecoin.stablecoin_scale = StablecoinState::decimals_scale(STABLECOIN_DECIMALS)?;
+    stablecoin.yield_scale = StablecoinState::decimals_scale(ctx.accounts.yield_mint.decimals)?;
+    stablecoin.fiat_oracle = ctx.accounts.fx_oracle
+        .as_ref()
+        .map(|fx_oracle| fx_oracle.key())
+        .unwrap_or_default();
     stablecoin.last_updated = Clock::get()?.unix_timestamp;
     stablecoin.last_rebase = Clock::get()?.unix_timestamp;
     stablecoin.total_rebase_amount = 0;
 
     stablecoin.user_shares = Vec::with_capacity(100);
     
+    // Initialize protocol parameters
+    stablecoin.collateral_ratio = collateral_ratio;
+    stablecoin.fee_rate = fee_rate;
+    stablecoin.stability_fee_bps = stability_fee_bps;
+    stablecoin.stability_fee_index = RATE_INDEX_SCALE;
+    stablecoin.last_stability_fee_accrual = Clock::get()?.unix_timestamp;
+    stablecoin.total_stability_fees = 0;
+    stablecoin.param_overrides = StablecoinParamOverrides::default();
+    stablecoin.kyc_policy = KycPolicy::Required;
+    stablecoin.kyc_requirements = KycRequirements::default();
+    stablecoin.compliance_mode = compliance_mode;
+    stablecoin.mint_policy = MintPolicy::Open;
+    stablecoin.flow_limits = FlowLimits::default();
+    stablecoin.flow_window = FlowWindow::default();
+
     // Initialize tracking
     stablecoin.total_supply = 0;
     stablecoin.total_collateral = 0;
     stablecoin.bump = ctx.bumps.stablecoin_state;
 
+    // Claim the symbol
+    let symbol_record = &mut ctx.accounts.symbol_record;
+    symbol_record.symbol = symbol.clone();
+    symbol_record.stablecoin = ctx.accounts.stablecoin_state.key();
+    symbol_record.is_reserved = false;
+    symbol_record.bump = ctx.bumps.symbol_record;
+
+    // Record the enumeration entry before bumping the count
+    let stablecoin_index = &mut ctx.accounts.stablecoin_index;
+    stablecoin_index.factory = ctx.accounts.factory_state.key();
+    stablecoin_index.index = ctx.accounts.factory_state.stablecoin_count;
+    stablecoin_index.stablecoin_state = ctx.accounts.stablecoin_state.key();
+    stablecoin_index.mint = ctx.accounts.mint.key();
+    stablecoin_index.bond_mint = ctx.accounts.bond_mint.key();
+    stablecoin_index.creator = ctx.accounts.creator.key();
+    stablecoin_index.bump = ctx.bumps.stablecoin_index;
+
     // Update factory
     let factory = &mut ctx.accounts.factory_state;
-    factory.stablecoin_count += 1;
+    factory.stablecoin_count = factory.stablecoin_count
+        .checked_add(1)
+        .ok_or(StablecoinError::MathOverflow)?;
     msg!("Stablecoin created! Now initializing user state...");
 
     // Initialize creator's user state
@@ -163,17 +513,22 @@ pub fn create_stablecoin(
     creator_state.stablecoin = ctx.accounts.mint.key();
     creator_state.total_yield_collected = 0;
     creator_state.last_yield_collection = Clock::get()?.unix_timestamp;
-    creator_state.bond_amount = 0;
-    creator_state.stablecoin_amount = 0;
     msg!("User state successfully initialized!");
 
     emit!(StablecoinCreated {
+        factory: ctx.accounts.factory_state.key(),
         creator: ctx.accounts.creator.key(),
         mint: ctx.accounts.mint.key(),
         bond_mint: ctx.accounts.bond_mint.key(),
         name: name.clone(),
         symbol: symbol.clone(),
         target_currency: target_currency.clone(),
+        uri,
+        collateral_ratio,
+        fee_rate,
+        token_program: ctx.accounts.stablecoin_token_program.key(),
+        index: ctx.accounts.stablecoin_index.index,
+        compliance_mode,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
@@ -0,0 +1,104 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+use crate::states::{factory_state::FactoryState, legacy::FactoryStateV1};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// MigrateFactory converts the factory account to the current schema version,
+/// reallocating it to the new size. The account is taken unchecked because an
+/// old layout cannot be deserialized as the current FactoryState.
+#[derive(Accounts)]
+pub struct MigrateFactory<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Seeds and owner are verified here, the layout is decoded in logic
+    #[account(
+        mut,
+        seeds = [FACTORY_STATE_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub factory_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateFactory<'info> {
+    /// Decodes the stored layout by explicit schema version. Version 1 predates
+    /// versioning and is recognized by its exact allocation size.
+    pub fn load_and_upgrade(&self) -> Result<(FactoryState, u16)> {
+        let data = self.factory_state.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == FactoryState::DISCRIMINATOR,
+            StablecoinError::InvalidAccountData
+        );
+
+        if data.len() == 8 + FactoryStateV1::INIT_SPACE {
+            let legacy = FactoryStateV1::deserialize(&mut &data[8..])?;
+            return Ok((legacy.into_current(), 1));
+        }
+
+        let current = FactoryState::try_deserialize(&mut &data[..])?;
+        require!(!current.is_current(), StablecoinError::AlreadyMigrated);
+        err!(StablecoinError::UnsupportedSchemaVersion)
+    }
+}
+
+pub fn migrate_factory(
+    ctx: Context<MigrateFactory>,
+) -> Result<()> {
+    let (factory_state, from_version) = ctx.accounts.load_and_upgrade()?;
+    require!(
+        factory_state.admin == ctx.accounts.admin.key(),
+        StablecoinError::Unauthorized
+    );
+
+    let account = ctx.accounts.factory_state.to_account_info();
+    realloc_account(
+        &account,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + FactoryState::INIT_SPACE,
+    )?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+    factory_state.try_serialize(&mut &mut data[..])?;
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        from_version,
+        to_version: FACTORY_SCHEMA_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Resizes a program-owned account, topping up rent from the payer
+pub fn realloc_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let current_lamports = account.lamports();
+
+    if required_lamports > current_lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                }
+            ),
+            required_lamports - current_lamports
+        )?;
+    }
+
+    account.realloc(new_len, false)?;
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+use anchor_spl::token::Mint;
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState, legacy::StablecoinStateV1, symbol_registry::SymbolRecord};
+use crate::contexts::migrate_factory::realloc_account;
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// MigrateStablecoin converts a stablecoin account to the current schema
+/// version and registers its symbol, which coins created before the symbol
+/// registry never claimed. Symbols from before the registry's format rules are
+/// normalized first; the original symbol still seeds the PDA. The factory must
+/// be migrated first.
+#[derive(Accounts)]
+pub struct MigrateStablecoin<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    /// CHECK: Owner and discriminator are verified, the layout is decoded in logic
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub stablecoin_state: UncheckedAccount<'info>,
+
+    /// CHECK: Registry claim on the coin's symbol, derived and created or claimed in logic
+    #[account(mut)]
+    pub symbol_record: UncheckedAccount<'info>,
+
+    /// The coin's stablebond mint, checked against the decoded state in logic
+    pub bond_mint: Box<Account<'info, Mint>>,
+
+    /// The coin's yield mint, checked against the decoded state in logic
+    pub yield_mint: Box<Account<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateStablecoin<'info> {
+    /// Decodes the stored layout by explicit schema version. Version 1 predates
+    /// versioning and is recognized by its exact allocation size.
+    pub fn load_and_upgrade(&self) -> Result<(StablecoinState, u16)> {
+        let data = self.stablecoin_state.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == StablecoinState::DISCRIMINATOR,
+            StablecoinError::InvalidAccountData
+        );
+
+        if data.len() == 8 + StablecoinStateV1::INIT_SPACE {
+            // Version 1 predates multiple factories, so the coin belongs to the singleton
+            require!(self.factory_state.factory_id == 0, StablecoinError::InvalidFactory);
+            let legacy = StablecoinStateV1::deserialize(&mut &data[8..])?;
+            require!(self.bond_mint.key() == legacy.bond_mint, StablecoinError::InvalidBondMint);
+            require!(self.yield_mint.key() == legacy.yield_mint, StablecoinError::InvalidFeeMint);
+
+            let bond_scale = StablecoinState::decimals_scale(self.bond_mint.decimals)?;
+            let yield_scale = StablecoinState::decimals_scale(self.yield_mint.decimals)?;
+            return Ok((legacy.into_current(self.factory_state.key(), bond_scale, yield_scale), 1));
+        }
+
+        let current = StablecoinState::try_deserialize(&mut &data[..])?;
+        require!(!current.is_current(), StablecoinError::AlreadyMigrated);
+        err!(StablecoinError::UnsupportedSchemaVersion)
+    }
+
+    /// Creates the registry claim on `stablecoin`'s symbol, or takes over the
+    /// admin's reservation of it. Fails if another coin holds the symbol.
+    pub fn claim_symbol(&self, stablecoin: &StablecoinState) -> Result<()> {
+        let symbol = &stablecoin.symbol;
+        SymbolRecord::validate_symbol(symbol)?;
+
+        let factory_key = self.factory_state.key();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[SYMBOL_REGISTRY_SEED, factory_key.as_ref(), symbol.as_bytes()],
+            &crate::ID,
+        );
+        require!(
+            expected == self.symbol_record.key(),
+            StablecoinError::InvalidAccountData
+        );
+
+        let record_info = self.symbol_record.to_account_info();
+        if record_info.data_is_empty() {
+            // Fund, allocate and assign separately rather than create_account,
+            // which anyone could block by sending lamports to the address first
+            let space = 8 + SymbolRecord::INIT_SPACE;
+            let shortfall = Rent::get()?
+                .minimum_balance(space)
+                .saturating_sub(record_info.lamports());
+            if shortfall > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.admin.to_account_info(),
+                            to: record_info.clone(),
+                        }
+                    ),
+                    shortfall,
+                )?;
+            }
+
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                SYMBOL_REGISTRY_SEED,
+                factory_key.as_ref(),
+                symbol.as_bytes(),
+                &[bump],
+            ]];
+            system_program::allocate(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Allocate { account_to_allocate: record_info.clone() },
+                    signer_seeds
+                ),
+                space as u64,
+            )?;
+            system_program::assign(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Assign { account_to_assign: record_info.clone() },
+                    signer_seeds
+                ),
+                &crate::ID,
+            )?;
+        } else {
+            require!(
+                record_info.owner == &crate::ID,
+                StablecoinError::InvalidAccountData
+            );
+            let record = SymbolRecord::try_deserialize(&mut &record_info.try_borrow_data()?[..])?;
+            require!(
+                record.stablecoin == self.stablecoin_state.key()
+                    || (record.is_reserved && record.stablecoin == Pubkey::default()),
+                StablecoinError::SymbolTaken
+            );
+        }
+
+        let record = SymbolRecord {
+            symbol: symbol.clone(),
+            stablecoin: self.stablecoin_state.key(),
+            is_reserved: false,
+            bump,
+        };
+        record.try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
+
+pub fn migrate_stablecoin(
+    ctx: Context<MigrateStablecoin>,
+) -> Result<()> {
+    let (mut stablecoin_state, from_version) = ctx.accounts.load_and_upgrade()?;
+
+    // Re-derive the PDA from the decoded seeds to confirm the account
+    let (expected, _) = Pubkey::find_program_address(
+        &[
+            STABLECOIN_SEED,
+            stablecoin_state.creator.as_ref(),
+            stablecoin_state.original_symbol.as_bytes(),
+        ],
+        &crate::ID,
+    );
+    require!(
+        expected == ctx.accounts.stablecoin_state.key(),
+        StablecoinError::InvalidAccountData
+    );
+
+    stablecoin_state.symbol = SymbolRecord::normalize_symbol(&stablecoin_state.symbol);
+    ctx.accounts.claim_symbol(&stablecoin_state)?;
+
+    let account = ctx.accounts.stablecoin_state.to_account_info();
+    realloc_account(
+        &account,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + StablecoinState::INIT_SPACE,
+    )?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+    stablecoin_state.try_serialize(&mut &mut data[..])?;
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        from_version,
+        to_version: STABLECOIN_SCHEMA_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
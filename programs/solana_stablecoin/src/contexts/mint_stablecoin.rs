@@ -1,13 +1,16 @@
 use anchor_lang::{prelude::*, system_program};
-use stablebond_sdk::find_kyc_pda;
 use switchboard_solana::AggregatorAccountData;
-use crate::{states::{factory_state::FactoryState, stablecoin::StablecoinState}, user::{UserShare, UserState}, sol_fee_vault::SolFeeVault};
+use crate::{states::{factory_state::FactoryState, kyc_attestation::KycAttestation, stablecoin::StablecoinState, minter_allowlist::MinterAllowlistEntry, flow_limits::{record_flow, FlowDirection}}, user::{UserShare, UserState}, sol_fee_vault::SolFeeVault};
 use anchor_spl::{
-    associated_token::AssociatedToken, token::{self, Mint, Token, TokenAccount}
+    associated_token::AssociatedToken, token::{self, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
 };
 use crate::errors::StablecoinError;
 use crate::constants::*;
 use crate::events::*;
+use crate::compliance::{verify_holder, verify_mint_policy};
+use crate::oracle::load_bond_price;
+use crate::contexts::accrue_stability_fee::collect_stability_fee;
 
 #[derive(Accounts)]
 pub struct MintStablecoin<'info> {
@@ -16,14 +19,18 @@ pub struct MintStablecoin<'info> {
 
     #[account(
         mut,
-        constraint = !stablecoin_state.is_paused @ StablecoinError::StablecoinPaused
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
     )]
     pub stablecoin_state: Box<Account<'info, StablecoinState>>,
 
     #[account(
         mut,
-        seeds = [FACTORY_STATE_SEED],
-        bump
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
@@ -55,15 +62,16 @@ pub struct MintStablecoin<'info> {
         payer = user,
         associated_token::mint = stablecoin_mint,
         associated_token::authority = user_state,
+        associated_token::token_program = stablecoin_token_program,
     )]
-    pub user_stablecoin_account: Account<'info, TokenAccount>,
+    pub user_stablecoin_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
     /// The stablecoin mint
     #[account(
         mut,
         address = stablecoin_state.mint
     )]
-    pub stablecoin_mint: Box<Account<'info, Mint>>,
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     /// Collateral vault
     #[account(
@@ -74,129 +82,91 @@ pub struct MintStablecoin<'info> {
 
     #[account(
         mut,
-        seeds = [SOL_FEE_VAULT_SEED],
+        seeds = [SOL_FEE_VAULT_SEED, factory_state.id_seed().as_ref()],
         bump = sol_fee_vault.bump,
     )]
     pub sol_fee_vault: Box<Account<'info, SolFeeVault>>,
 
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
     /// CHECK: Verified in logic
     pub kyc_info: AccountInfo<'info>,
 
+    /// Holder's tier and jurisdiction, needed when the stablecoin sets KYC requirements
+    #[account(
+        seeds = [KYC_ATTESTATION_SEED, factory_state.key().as_ref(), user.key().as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// CHECK: The user's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The user's minter approval, required when the mint policy is AllowlistOnly
+    #[account(
+        seeds = [MINTER_ALLOWLIST_SEED, stablecoin_state.key().as_ref(), user.key().as_ref()],
+        bump = allowlist_entry.bump
+    )]
+    pub allowlist_entry: Option<Account<'info, MinterAllowlistEntry>>,
+
     // Switchboard oracle accounts
     pub oracle: AccountLoader<'info, AggregatorAccountData>,
 
+    /// FX feed into the target currency, required when the stablecoin has one
+    pub fx_oracle: Option<AccountLoader<'info, AggregatorAccountData>>,
+
     // Programs
     pub token_program: Program<'info, Token>,
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> MintStablecoin<'info> {
-    pub fn validate(&self, amount: u64) -> Result<()> {
-        msg!("Starting validation for mint amount: {}", amount);
-
-        // 1. Verify KYC using Etherfuse
-        let (kyc_pda, _) = find_kyc_pda(self.user.key());
-        require!(
-            self.kyc_info.key() == kyc_pda,
-            StablecoinError::InvalidKycAccount
-        );
-
-        // Verify the account exists and is owned by Etherfuse program        
-        require!(
-            self.kyc_info.owner == &stablebond_sdk::ID,
-            StablecoinError::InvalidKycAccount
-        );
-
-        // 2. Check minimum deposit
-        let bond_config = self.factory_state.get_bond_config(
-            &self.stablecoin_state.bond_mint
-        ).ok_or(StablecoinError::BondNotFound)?;
-
-        require!(
-            amount >= bond_config.min_creation_amount,
-            StablecoinError::DepositTooSmall
-        );
-
-        // 3. Verify oracle staleness
-        let clock = Clock::get()?;
-        require!(
-            clock.unix_timestamp - self.stablecoin_state.last_price_update 
-                <= ORACLE_STALENESS_THRESHOLD,
-            StablecoinError::StaleOraclePrice
-        );
+    pub fn validate(&self) -> Result<()> {
+        msg!("Starting validation for mint");
+
+        // KYC, denylist and mint policy, shared with quote_mint
+        verify_holder(
+            &self.stablecoin_state,
+            &self.user.key(),
+            &self.kyc_info,
+            self.kyc_attestation.as_deref(),
+            &self.denylist_entry,
+        )?;
+        verify_mint_policy(
+            &self.stablecoin_state,
+            &self.user.key(),
+            &self.kyc_info,
+            self.allowlist_entry.is_some(),
+        )?;
 
         Ok(())
     }
     
     pub fn get_oracle_price(&self) -> Result<u64> {
         msg!("Fetching oracle price");
-        
-        let feed = self.oracle.load()?;
-        // Convert to absolute value since prices can't be negative
-        let mantissa = feed.latest_confirmed_round.result.mantissa.abs() as u128;
-        
-        let price = mantissa
-            .checked_mul(PRICE_SCALE)
-            .ok_or(StablecoinError::MathOverflow)?;
-    
-        require!(price > 0, StablecoinError::InvalidOraclePrice);
-        
-        Ok(price as u64)
-    }
 
-    pub fn calculate_mint_amount(
-        &self,
-        bond_amount: u64,
-        bond_price: u64,    // Price scaled by PRICE_SCALE
-        collateral_ratio: u16,
-    ) -> Result<u64> {
-        msg!("Calculating mint amount for {} bonds at price {}", 
-            bond_amount, bond_price);
-
-        // Calculate total collateral value 
-        let collateral_value = (bond_amount as u128)
-            .checked_mul(bond_price as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-        
-        // Calculate mint amount based on collateral ratio
-        // collateral_value * BPS_SCALE / collateral_ratio
-        let mint_amount = collateral_value
-            .checked_mul(BPS_SCALE as u128)
-            .ok_or(StablecoinError::MathOverflow)?
-            .checked_div(collateral_ratio as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-            
-        // Scale back down
-        let mint_amount = mint_amount
-            .checked_div(PRICE_SCALE)
-            .ok_or(StablecoinError::MathOverflow)?;
-            
-        require!(
-            mint_amount <= u64::MAX as u128,
-            StablecoinError::ExcessivePriceDeviation
-        );
-            
-        Ok(mint_amount as u64)
-    }
-
-    pub fn calculate_fee_amount(&self, amount: u64) -> Result<u64> {
-        let fee_rate = self.factory_state
-            .get_fee_rate(&self.stablecoin_state.bond_mint)?;
-            
-        let fee_amount = (amount as u128)
-            .checked_mul(fee_rate as u128)
-            .ok_or(StablecoinError::MathOverflow)?
-            .checked_div(BPS_SCALE as u128)
-            .ok_or(StablecoinError::MathOverflow)?;
-    
-        require!(
-            fee_amount <= u64::MAX as u128,
-            StablecoinError::FeeTooLarge
-        );
-            
-        Ok(fee_amount as u64)
+        load_bond_price(
+            &self.oracle,
+            &self.stablecoin_state,
+            &self.fx_oracle,
+            self.stablecoin_state.oracle_staleness_threshold(&self.factory_state),
+        )
     }
 
     pub fn collect_fees(
@@ -215,7 +185,7 @@ impl<'info> MintStablecoin<'info> {
                     to: ctx.accounts.sol_fee_vault.to_account_info(),
                 }
             ),
-            fee_amount as u64
+            fee_amount
         )?;
     
         Ok(())
@@ -264,26 +234,42 @@ impl<'info> MintStablecoin<'info> {
 
 pub fn mint_stablecoin(
     ctx: Context<MintStablecoin>, 
-    bond_amount: u64
+    bond_amount: u64,
+    min_stablecoin_out: u64,
+    max_fee: u64,
+    expires_at: Option<i64>,
 ) -> Result<()> {
     msg!("Starting stablecoin mint process");
 
     // 1. Validate all conditions
-    ctx.accounts.validate(bond_amount)?;
+    if let Some(expires_at) = expires_at {
+        require!(
+            Clock::get()?.unix_timestamp <= expires_at,
+            StablecoinError::TransactionExpired
+        );
+    }
+    ctx.accounts.validate()?;
+
+    // Bring every position's stability fee up to date
+    collect_stability_fee(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        ctx.accounts.stability_fee_vault.to_account_info(),
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+    )?;
 
     // 2. Get oracle price
     let bond_price = ctx.accounts.get_oracle_price()?;
     msg!("Current bond price (scaled): {}", bond_price);
 
-    // 3. Calculate mint amount
-    let mint_amount = ctx.accounts.calculate_mint_amount(
-        bond_amount,
-        bond_price,
-        ctx.accounts.stablecoin_state.collateral_ratio,
-    )?;
+    // 3. Calculate mint amount and fee
+    let (mint_amount, fee_amount) = ctx.accounts.stablecoin_state
+        .price_mint(&ctx.accounts.factory_state, bond_amount, bond_price)?;
     msg!("Calculated mint amount: {}", mint_amount);
 
-    let fee_amount = ctx.accounts.calculate_fee_amount(mint_amount)?;
+    // Protect the caller from price moves since they quoted
+    require!(mint_amount >= min_stablecoin_out, StablecoinError::ExcessiveSlippage);
+    require!(fee_amount <= max_fee, StablecoinError::FeeExceedsMaximum);
 
     // 4. Collect fees
     ctx.accounts.collect_fees(&ctx, fee_amount)?;
@@ -292,7 +278,7 @@ pub fn mint_stablecoin(
     let sol_fee_vault = &mut ctx.accounts.sol_fee_vault;
     sol_fee_vault.total_fees_collected = sol_fee_vault
         .total_fees_collected
-        .checked_add(fee_amount as u64)
+        .checked_add(fee_amount)
         .ok_or(StablecoinError::MathOverflow)?;
     sol_fee_vault.last_collection = Clock::get()?.unix_timestamp;
 
@@ -316,11 +302,30 @@ pub fn mint_stablecoin(
         mint_amount,
     )?;
 
+    // A first mint creates the user state; record its seeds so later
+    // instructions can re-derive it and sign with it
+    let user_state = &mut ctx.accounts.user_state;
+    if user_state.user == Pubkey::default() {
+        user_state.bump = ctx.bumps.user_state;
+        user_state.user = ctx.accounts.user.key();
+        user_state.stablecoin = ctx.accounts.stablecoin_mint.key();
+        user_state.last_yield_collection = Clock::get()?.unix_timestamp;
+    }
+
+    // Count the mint against the stablecoin's and the minter's flow limits
+    record_flow(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.user.key(),
+        &mut user_state.flow_window,
+        FlowDirection::Mint,
+        mint_amount,
+    )?;
+
     // 7. Mint stablecoins to user
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::MintTo {
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::MintTo {
                 mint: ctx.accounts.stablecoin_mint.to_account_info(),
                 to: ctx.accounts.user_stablecoin_account.to_account_info(),
                 authority: ctx.accounts.stablecoin_state.to_account_info(),
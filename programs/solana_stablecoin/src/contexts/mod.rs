@@ -4,18 +4,81 @@ pub use init_factory::*;
 pub mod update_factory;
 pub use update_factory::*;
 
+pub mod update_protocol_params;
+pub use update_protocol_params::*;
+
 pub mod init_stablecoin;
 pub use init_stablecoin::*;
 
 pub mod update_stablecoin;
 pub use update_stablecoin::*;
 
+pub mod transfer_stablecoin_authority;
+pub use transfer_stablecoin_authority::*;
+
+pub mod accept_stablecoin_authority;
+pub use accept_stablecoin_authority::*;
+
+pub mod set_stablecoin_operators;
+pub use set_stablecoin_operators::*;
+
+pub mod reserve_symbol;
+pub use reserve_symbol::*;
+
+pub mod release_symbol;
+pub use release_symbol::*;
+
+pub mod set_stablecoin_param_overrides;
+pub use set_stablecoin_param_overrides::*;
+
+pub mod set_kyc_policy;
+pub use set_kyc_policy::*;
+
+pub mod attest_kyc;
+pub use attest_kyc::*;
+
+pub mod add_to_denylist;
+pub use add_to_denylist::*;
+
+pub mod remove_from_denylist;
+pub use remove_from_denylist::*;
+
+pub mod freeze_holder;
+pub use freeze_holder::*;
+
+pub mod thaw_holder;
+pub use thaw_holder::*;
+
+pub mod seize_holder;
+pub use seize_holder::*;
+
+pub mod set_mint_policy;
+pub use set_mint_policy::*;
+
+pub mod add_minter;
+pub use add_minter::*;
+
+pub mod remove_minter;
+pub use remove_minter::*;
+
+pub mod update_interest_rate;
+pub use update_interest_rate::*;
+
 pub mod burn_stablecoin;
 pub use burn_stablecoin::*;
 
 pub mod mint_stablecoin;
 pub use mint_stablecoin::*;
 
+pub mod redeem;
+pub use redeem::*;
+
+pub mod deposit_collateral;
+pub use deposit_collateral::*;
+
+pub mod withdraw_collateral;
+pub use withdraw_collateral::*;
+
 pub mod add_bond;
 pub use add_bond::*;
 
@@ -28,8 +91,49 @@ pub use remove_bond::*;
 pub mod distribute_yield;
 pub use distribute_yield::*;
 
+pub mod migrate_factory;
+pub use migrate_factory::*;
+
+pub mod migrate_stablecoin;
+pub use migrate_stablecoin::*;
+
+pub mod shutdown_stablecoin;
+pub use shutdown_stablecoin::*;
+
+pub mod settle_stablecoin;
+pub use settle_stablecoin::*;
+
 pub mod pause_stablecoin;
 pub use pause_stablecoin::*;
 
 pub mod resume_stablecoin;
-pub use resume_stablecoin::*;
\ No newline at end of file
+pub use resume_stablecoin::*;
+pub mod quote_mint;
+pub use quote_mint::*;
+
+pub mod quote_burn;
+pub use quote_burn::*;
+
+pub mod quote_yield;
+pub use quote_yield::*;
+
+pub mod set_flow_limits;
+pub use set_flow_limits::*;
+
+pub mod quote_flow_capacity;
+pub use quote_flow_capacity::*;
+
+pub mod accrue_stability_fee;
+pub use accrue_stability_fee::*;
+
+pub mod set_stability_fee;
+pub use set_stability_fee::*;
+
+pub mod withdraw_stability_fees;
+pub use withdraw_stability_fees::*;
+
+pub mod repay_debt;
+pub use repay_debt::*;
+
+pub mod set_fx_feed;
+pub use set_fx_feed::*;
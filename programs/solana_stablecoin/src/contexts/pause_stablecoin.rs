@@ -4,23 +4,27 @@ use crate::errors::StablecoinError;
 use crate::constants::*;
 use crate::events::*;
 
+/// PauseStablecoin has two independent paths: the factory guardian can pause
+/// any coin, and the coin's authority (or its pauser) can pause its own coin.
+/// Each path sets its own flag so neither can lift the other's pause.
 #[derive(Accounts)]
 pub struct PauseStablecoin<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
     #[account(
-        mut,
-        seeds = [FACTORY_STATE_SEED],
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
         bump = factory_state.bump,
-        constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused,
-        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
     #[account(
         mut,
-        constraint = stablecoin_state.creator == admin.key() @ StablecoinError::Unauthorized,
+        constraint = factory_state.guardian == admin.key()
+            || stablecoin_state.can_pause(&admin.key()) @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
     )]
     pub stablecoin_state: Box<Account<'info, StablecoinState>>,
 
@@ -30,19 +34,27 @@ pub struct PauseStablecoin<'info> {
 pub fn pause_stablecoin(
     ctx: Context<PauseStablecoin>,
 ) -> Result<()> {
+    // The guardian path takes precedence when the signer holds both roles
+    let by_factory = ctx.accounts.factory_state.guardian == ctx.accounts.admin.key();
+
     // Set pause state
     let stablecoin = &mut ctx.accounts.stablecoin_state;
-    require!(!stablecoin.is_paused, StablecoinError::AlreadyPaused);
-    
-    stablecoin.is_paused = true;
+    if by_factory {
+        require!(!stablecoin.paused_by_factory, StablecoinError::AlreadyPaused);
+        stablecoin.paused_by_factory = true;
+    } else {
+        require!(!stablecoin.paused_by_authority, StablecoinError::AlreadyPaused);
+        stablecoin.paused_by_authority = true;
+    }
     stablecoin.last_updated = Clock::get()?.unix_timestamp;
 
     // Emit event
     emit!(StablecoinPaused {
         admin: ctx.accounts.admin.key(),
         stablecoin: ctx.accounts.stablecoin_state.key(),
+        by_factory,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
-}
\ No newline at end of file
+}
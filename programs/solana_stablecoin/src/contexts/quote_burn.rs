@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use switchboard_solana::AggregatorAccountData;
+use crate::states::{
+    factory_state::FactoryState, kyc_attestation::KycAttestation, stablecoin::StablecoinState,
+    user::UserState, flow_limits::{check_flow, FlowDirection}, quote::BurnQuote,
+};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::compliance::verify_holder;
+use crate::oracle::load_bond_price;
+
+/// QuoteBurn runs burn_tokens' checks and math without moving funds, so clients
+/// can simulate it to preview the outcome. The stability fee is accrued on a
+/// copy of the state first, as burn_tokens would.
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct QuoteBurn<'info> {
+    #[account(
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    /// The burner's state
+    #[account(
+        seeds = [
+            USER_STATE_SEED,
+            user.as_ref(),
+            stablecoin_state.mint.as_ref()
+        ],
+        bump = user_state.bump
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    /// CHECK: Verified in logic
+    pub kyc_info: AccountInfo<'info>,
+
+    /// Burner's tier and jurisdiction, needed when the stablecoin sets KYC requirements
+    #[account(
+        seeds = [KYC_ATTESTATION_SEED, factory_state.key().as_ref(), user.as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// CHECK: The burner's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// Collateral vault
+    #[account(address = stablecoin_state.collateral_vault)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    // Switchboard oracle accounts
+    pub oracle: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed into the target currency, required when the stablecoin has one
+    pub fx_oracle: Option<AccountLoader<'info, AggregatorAccountData>>,
+}
+
+impl<'info> QuoteBurn<'info> {
+    pub fn validate(&self, user: &Pubkey) -> Result<()> {
+        verify_holder(
+            &self.stablecoin_state,
+            user,
+            &self.kyc_info,
+            self.kyc_attestation.as_deref(),
+            &self.denylist_entry,
+        )
+    }
+}
+
+pub fn quote_burn(
+    ctx: Context<QuoteBurn>,
+    user: Pubkey,
+    stablecoin_amount: u64,
+) -> Result<BurnQuote> {
+    ctx.accounts.validate(&user)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut stablecoin = StablecoinState::clone(&ctx.accounts.stablecoin_state);
+    stablecoin.accrue_stability_fee(now)?;
+
+    let bond_price = load_bond_price(
+        &ctx.accounts.oracle,
+        &stablecoin,
+        &ctx.accounts.fx_oracle,
+        stablecoin.oracle_staleness_threshold(&ctx.accounts.factory_state),
+    )?;
+
+    let (bond_amount, fee) = stablecoin.price_burn(&ctx.accounts.factory_state, &user, stablecoin_amount)?;
+    require!(
+        ctx.accounts.collateral_vault.amount >= bond_amount,
+        StablecoinError::InsufficientCollateral
+    );
+
+    check_flow(&stablecoin, &ctx.accounts.user_state.flow_window, FlowDirection::Redeem, stablecoin_amount, now)?;
+
+    // Health of the burner's position and of the pool once the burn lands
+    let share = stablecoin.user_shares
+        .iter()
+        .find(|s| s.owner == user)
+        .ok_or(StablecoinError::UserShareNotFound)?;
+
+    let position_ratio = stablecoin.collateral_ratio_for(
+        share.bond_amount.checked_sub(bond_amount).ok_or(StablecoinError::MathOverflow)?,
+        share.mint_amount.checked_sub(stablecoin_amount).ok_or(StablecoinError::MathOverflow)?,
+        bond_price,
+    )?;
+
+    let pool_ratio = stablecoin.collateral_ratio_for(
+        stablecoin.total_collateral.saturating_sub(bond_amount),
+        stablecoin.total_supply.saturating_sub(stablecoin_amount),
+        bond_price,
+    )?;
+
+    Ok(BurnQuote {
+        stablecoin_amount,
+        bond_amount,
+        fee,
+        bond_price,
+        position_ratio,
+        pool_ratio,
+    })
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::states::{stablecoin::StablecoinState, user::UserState, flow_limits::{FlowDirection, FlowWindow}, quote::FlowCapacity};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+
+/// QuoteFlowCapacity reports how much can still be minted or redeemed before
+/// the stablecoin's flow limits are hit, overall and for one holder
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct QuoteFlowCapacity<'info> {
+    #[account(
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    /// The holder's state, omitted if they have never minted
+    #[account(
+        seeds = [
+            USER_STATE_SEED,
+            user.as_ref(),
+            stablecoin_state.mint.as_ref()
+        ],
+        bump
+    )]
+    pub user_state: Option<Box<Account<'info, UserState>>>,
+}
+
+pub fn quote_flow_capacity(
+    ctx: Context<QuoteFlowCapacity>,
+    _user: Pubkey,
+) -> Result<FlowCapacity> {
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin = &ctx.accounts.stablecoin_state;
+    let limits = &stablecoin.flow_limits;
+    let user_window = ctx.accounts.user_state
+        .as_ref()
+        .map(|user_state| user_state.flow_window)
+        .unwrap_or_default();
+
+    let window_end = |window: &FlowWindow| {
+        let end = window.window_end(limits.window_duration);
+        if limits.is_enabled() && end > now { end } else { 0 }
+    };
+
+    Ok(FlowCapacity {
+        mint_remaining: stablecoin.flow_window.remaining(
+            FlowDirection::Mint, limits.max_net_mint, limits.window_duration, now
+        ),
+        redeem_remaining: stablecoin.flow_window.remaining(
+            FlowDirection::Redeem, limits.max_net_redeem, limits.window_duration, now
+        ),
+        user_mint_remaining: user_window.remaining(
+            FlowDirection::Mint, limits.user_max_net_mint, limits.window_duration, now
+        ),
+        user_redeem_remaining: user_window.remaining(
+            FlowDirection::Redeem, limits.user_max_net_redeem, limits.window_duration, now
+        ),
+        window_ends_at: window_end(&stablecoin.flow_window),
+        user_window_ends_at: window_end(&user_window),
+    })
+}
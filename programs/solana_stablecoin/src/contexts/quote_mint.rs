@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+use crate::states::{
+    factory_state::FactoryState, kyc_attestation::KycAttestation, minter_allowlist::MinterAllowlistEntry,
+    stablecoin::StablecoinState, user::UserState, flow_limits::{check_flow, FlowDirection}, quote::MintQuote,
+};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::compliance::{verify_holder, verify_mint_policy};
+use crate::oracle::load_bond_price;
+
+/// QuoteMint runs mint_tokens' checks and math without moving funds, so clients
+/// can simulate it to preview the outcome. The stability fee is accrued on a
+/// copy of the state first, as mint_tokens would.
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct QuoteMint<'info> {
+    #[account(
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    /// The minter's state, omitted if they have never minted
+    #[account(
+        seeds = [
+            USER_STATE_SEED,
+            user.as_ref(),
+            stablecoin_state.mint.as_ref()
+        ],
+        bump
+    )]
+    pub user_state: Option<Box<Account<'info, UserState>>>,
+
+    /// CHECK: Verified in logic
+    pub kyc_info: AccountInfo<'info>,
+
+    /// Minter's tier and jurisdiction, needed when the stablecoin sets KYC requirements
+    #[account(
+        seeds = [KYC_ATTESTATION_SEED, factory_state.key().as_ref(), user.as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// CHECK: The minter's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    /// The minter's approval, required when the mint policy is AllowlistOnly
+    #[account(
+        seeds = [MINTER_ALLOWLIST_SEED, stablecoin_state.key().as_ref(), user.as_ref()],
+        bump = allowlist_entry.bump
+    )]
+    pub allowlist_entry: Option<Account<'info, MinterAllowlistEntry>>,
+
+    // Switchboard oracle accounts
+    pub oracle: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed into the target currency, required when the stablecoin has one
+    pub fx_oracle: Option<AccountLoader<'info, AggregatorAccountData>>,
+}
+
+impl<'info> QuoteMint<'info> {
+    pub fn validate(&self, user: &Pubkey) -> Result<()> {
+        verify_holder(
+            &self.stablecoin_state,
+            user,
+            &self.kyc_info,
+            self.kyc_attestation.as_deref(),
+            &self.denylist_entry,
+        )?;
+        verify_mint_policy(
+            &self.stablecoin_state,
+            user,
+            &self.kyc_info,
+            self.allowlist_entry.is_some(),
+        )
+    }
+}
+
+pub fn quote_mint(
+    ctx: Context<QuoteMint>,
+    user: Pubkey,
+    bond_amount: u64,
+) -> Result<MintQuote> {
+    ctx.accounts.validate(&user)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut stablecoin = StablecoinState::clone(&ctx.accounts.stablecoin_state);
+    stablecoin.accrue_stability_fee(now)?;
+
+    let bond_price = load_bond_price(
+        &ctx.accounts.oracle,
+        &stablecoin,
+        &ctx.accounts.fx_oracle,
+        stablecoin.oracle_staleness_threshold(&ctx.accounts.factory_state),
+    )?;
+
+    let (mint_amount, fee) = stablecoin.price_mint(&ctx.accounts.factory_state, bond_amount, bond_price)?;
+
+    let user_window = ctx.accounts.user_state
+        .as_ref()
+        .map(|user_state| user_state.flow_window)
+        .unwrap_or_default();
+    check_flow(&stablecoin, &user_window, FlowDirection::Mint, mint_amount, now)?;
+
+    // Health of the minter's position and of the pool once the mint lands
+    let (position_bonds, position_debt) = stablecoin.user_shares
+        .iter()
+        .find(|s| s.owner == user)
+        .map_or((0, 0), |s| (s.bond_amount, s.mint_amount));
+
+    let position_ratio = stablecoin.collateral_ratio_for(
+        position_bonds.checked_add(bond_amount).ok_or(StablecoinError::MathOverflow)?,
+        position_debt.checked_add(mint_amount).ok_or(StablecoinError::MathOverflow)?,
+        bond_price,
+    )?;
+
+    let pool_ratio = stablecoin.collateral_ratio_for(
+        stablecoin.total_collateral.checked_add(bond_amount).ok_or(StablecoinError::MathOverflow)?,
+        stablecoin.total_supply.checked_add(mint_amount).ok_or(StablecoinError::MathOverflow)?,
+        bond_price,
+    )?;
+
+    Ok(MintQuote {
+        bond_amount,
+        mint_amount,
+        fee,
+        bond_price,
+        position_ratio,
+        pool_ratio,
+    })
+}
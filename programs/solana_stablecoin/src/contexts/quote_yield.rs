@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use switchboard_solana::AggregatorAccountData;
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState, quote::YieldQuote};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::oracle::{load_bond_price, load_yield_price};
+
+/// QuoteYield previews what distribute_yield would pay a position right now
+#[derive(Accounts)]
+pub struct QuoteYield<'info> {
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    /// Bond oracle for price feed
+    pub oracle: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed into the target currency, required when the stablecoin has one
+    pub fx_oracle: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: Verified through find_bond_pda
+    pub bond_info: AccountInfo<'info>,
+
+    /// The bond mint 
+    #[account(address = stablecoin_state.bond_mint)]
+    pub bond_mint: Box<Account<'info, Mint>>,
+}
+
+pub fn quote_yield(
+    ctx: Context<QuoteYield>,
+    user: Pubkey,
+) -> Result<YieldQuote> {
+    let (bond_pda, _) = stablebond_sdk::find_bond_pda(ctx.accounts.bond_mint.key());
+    require!(
+        bond_pda == ctx.accounts.bond_info.key(),
+        StablecoinError::InvalidBondAccount
+    );
+
+    let bond = stablebond_sdk::accounts::Bond::try_from_slice(
+        &ctx.accounts.bond_info.try_borrow_data()?
+    )?;
+
+    let factory = &ctx.accounts.factory_state;
+    let stablecoin = &ctx.accounts.stablecoin_state;
+
+    let yield_price = load_yield_price(
+        &ctx.accounts.oracle,
+        &bond.payment_feed_type,
+        factory.protocol_params.rate_freshness_threshold,
+    )?;
+
+    let (protocol_fee, user_yield) = stablecoin.rebase_yield(
+        factory,
+        &user,
+        yield_price,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let bond_price = load_bond_price(
+        &ctx.accounts.oracle,
+        stablecoin,
+        &ctx.accounts.fx_oracle,
+        stablecoin.oracle_staleness_threshold(factory),
+    )?;
+
+    let share = stablecoin.user_shares
+        .iter()
+        .find(|s| s.owner == user)
+        .ok_or(StablecoinError::NoUserPosition)?;
+    let position_ratio = stablecoin.position_ratio(share, bond_price)?;
+
+    Ok(YieldQuote {
+        user_yield,
+        protocol_fee,
+        yield_price: yield_price.min(u64::MAX as u128) as u64,
+        bond_price,
+        position_ratio,
+        next_rebase_at: stablecoin.last_rebase
+            .saturating_add(factory.protocol_params.rebase_interval),
+    })
+}
@@ -0,0 +1,373 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+use crate::{states::{factory_state::FactoryState, kyc_attestation::KycAttestation, stablecoin::{RedemptionMode, StablecoinState}, flow_limits::{record_flow, FlowDirection}}, user::UserState};
+use anchor_spl::{
+    associated_token::AssociatedToken, token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
+};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::kyc::{verify_kyc, verify_kyc_requirements};
+use crate::oracle::load_bond_price;
+use crate::math::{mul_div_u64, Rounding};
+use crate::contexts::accrue_stability_fee::collect_stability_fee;
+
+/// Redeem lets any holder swap stablecoins for stablebonds worth their face value
+/// at the current oracle price, minus a redemption fee. Collateral is drawn from the
+/// lowest-health positions first or pro-rata from the pool, which keeps the coin at peg.
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    /// User's state PDA, created for holders who never minted
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserState::INIT_SPACE,
+        seeds = [
+            USER_STATE_SEED,
+            user.key().as_ref(),
+            stablecoin_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    /// User's stablebond token account to receive bonds
+    #[account(
+        mut,
+        constraint = user_bond_account.owner == user.key() @ StablecoinError::InvalidTokenAccountOwner,
+        constraint = user_bond_account.mint == stablecoin_state.bond_mint @ StablecoinError::InvalidBondMint
+    )]
+    pub user_bond_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's stablecoin token account to burn from, held by the user or by their user state
+    #[account(
+        mut,
+        token::mint = stablecoin_mint,
+        token::token_program = stablecoin_token_program,
+        constraint = user_stablecoin_account.owner == user.key()
+            || user_stablecoin_account.owner == user_state.key() @ StablecoinError::InvalidTokenAccountOwner
+    )]
+    pub user_stablecoin_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        address = stablecoin_state.mint
+    )]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The stablebond mint backing this stablecoin
+    #[account(address = stablecoin_state.bond_mint)]
+    pub bond_mint: Box<Account<'info, Mint>>,
+
+    /// Collateral vault
+    #[account(
+        mut,
+        address = stablecoin_state.collateral_vault
+    )]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Protocol fee vault for redemption fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = bond_mint,
+        associated_token::authority = factory_state,
+    )]
+    pub bond_fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: Verified in logic
+    pub kyc_info: AccountInfo<'info>,
+
+    /// Holder's tier and jurisdiction, needed when the stablecoin sets KYC requirements
+    #[account(
+        seeds = [KYC_ATTESTATION_SEED, factory_state.key().as_ref(), user.key().as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// CHECK: The user's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    // Switchboard oracle accounts
+    pub oracle: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed into the target currency, required when the stablecoin has one
+    pub fx_oracle: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    // Programs
+    pub token_program: Program<'info, Token>,
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Redeem<'info> {
+    pub fn validate(&self, amount: u64) -> Result<()> {
+        msg!("Starting validation for redemption amount: {}", amount);
+
+        // 1. Verify KYC using Etherfuse, per the stablecoin's policy
+        verify_kyc(&self.kyc_info, &self.user.key(), self.stablecoin_state.kyc_policy)?;
+        verify_kyc_requirements(self.kyc_attestation.as_deref(), &self.user.key(), &self.stablecoin_state)?;
+
+        // Denylisted addresses can't redeem either
+        require!(
+            self.denylist_entry.data_is_empty(),
+            StablecoinError::AddressDenylisted
+        );
+
+        // 2. Check minimum redemption
+        let bond_config = self.factory_state.get_bond_config(
+            &self.stablecoin_state.bond_mint
+        ).ok_or(StablecoinError::BondNotFound)?;
+
+        require!(
+            amount >= bond_config.min_redemption_amount,
+            StablecoinError::RedeemAmountTooSmall
+        );
+
+        // 3. Verify user has enough stablecoins
+        require!(
+            self.user_stablecoin_account.amount >= amount,
+            StablecoinError::InsufficientStablecoinBalance
+        );
+
+        Ok(())
+    }
+
+    pub fn get_oracle_price(&self) -> Result<u64> {
+        msg!("Fetching oracle price");
+
+        load_bond_price(
+            &self.oracle,
+            &self.stablecoin_state,
+            &self.fx_oracle,
+            self.stablecoin_state.oracle_staleness_threshold(&self.factory_state),
+        )
+    }
+
+    /// Redemption fee in bonds, rounded up in the protocol's favor
+    pub fn calculate_redemption_fee(&self, bond_amount: u64) -> Result<u64> {
+        mul_div_u64(
+            bond_amount,
+            self.stablecoin_state.redemption_fee_bps(&self.factory_state) as u64,
+            BPS_SCALE as u64,
+            Rounding::Up,
+        )
+    }
+}
+
+pub fn redeem(
+    ctx: Context<Redeem>,
+    stablecoin_amount: u64,
+    mode: RedemptionMode,
+    min_bonds_out: u64,
+) -> Result<()> {
+    msg!("Starting peg redemption");
+
+    // 1. Validate all conditions
+    ctx.accounts.validate(stablecoin_amount)?;
+
+    let user_state = &mut ctx.accounts.user_state;
+    if user_state.user == Pubkey::default() {
+        user_state.bump = ctx.bumps.user_state;
+        user_state.user = ctx.accounts.user.key();
+        user_state.stablecoin = ctx.accounts.stablecoin_mint.key();
+        user_state.last_yield_collection = Clock::get()?.unix_timestamp;
+    }
+
+    // Bring every position's stability fee up to date
+    collect_stability_fee(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        ctx.accounts.stability_fee_vault.to_account_info(),
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+    )?;
+
+    // 2. Get oracle price
+    let bond_price = ctx.accounts.get_oracle_price()?;
+    msg!("Current bond price (scaled): {}", bond_price);
+
+    // 3. Draw face value from positions
+    let gross_bond_amount = ctx.accounts.stablecoin_state
+        .redeem_against_positions(stablecoin_amount, bond_price, mode, Clock::get()?.unix_timestamp)?;
+
+    require!(
+        ctx.accounts.collateral_vault.amount >= gross_bond_amount,
+        StablecoinError::InsufficientCollateral
+    );
+
+    // 4. Split off the redemption fee
+    let fee_amount = ctx.accounts.calculate_redemption_fee(gross_bond_amount)?;
+    let bond_amount = gross_bond_amount
+        .checked_sub(fee_amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+    msg!("Redeeming {} bonds with a fee of {}", bond_amount, fee_amount);
+
+    // Protect the caller from price moves since they quoted
+    require!(bond_amount >= min_bonds_out, StablecoinError::ExcessiveSlippage);
+
+    // 5. Burn stablecoins from user
+    burn_from_holder(
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        &ctx.accounts.user_stablecoin_account,
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.user_state.to_account_info(),
+        ctx.bumps.user_state,
+        stablecoin_amount,
+    )?;
+
+    let stablecoin_seeds: &[&[u8]] = &[
+        STABLECOIN_SEED,
+        ctx.accounts.stablecoin_state.creator.as_ref(),
+        ctx.accounts.stablecoin_state.original_symbol.as_bytes(),
+        &[ctx.accounts.stablecoin_state.bump],
+    ];
+
+    // 6. Transfer bonds from vault to user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.user_bond_account.to_account_info(),
+                authority: ctx.accounts.stablecoin_state.to_account_info(),
+            },
+            &[stablecoin_seeds]
+        ),
+        bond_amount
+    )?;
+
+    // 7. Accrue the redemption fee to the protocol fee vault
+    if fee_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    to: ctx.accounts.bond_fee_vault.to_account_info(),
+                    authority: ctx.accounts.stablecoin_state.to_account_info(),
+                },
+                &[stablecoin_seeds]
+            ),
+            fee_amount
+        )?;
+    }
+
+    // Count the redemption against the stablecoin's and the holder's flow limits
+    let user_state = &mut ctx.accounts.user_state;
+    record_flow(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.user.key(),
+        &mut user_state.flow_window,
+        FlowDirection::Redeem,
+        stablecoin_amount,
+    )?;
+
+    // 8. Update state
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    stablecoin.total_supply = stablecoin.total_supply
+        .checked_sub(stablecoin_amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+
+    stablecoin.total_collateral = stablecoin.total_collateral
+        .checked_sub(gross_bond_amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+
+    stablecoin.last_updated = Clock::get()?.unix_timestamp;
+
+    // 9. Emit event
+    emit!(StablecoinRedeemed {
+        user: ctx.accounts.user.key(),
+        mint: ctx.accounts.stablecoin_mint.key(),
+        stablecoin_amount,
+        bond_amount,
+        fee_amount,
+        bond_price,
+        mode,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Burns `amount` from a holder's stablecoin account. Balances minted into the
+/// user state's account are burned by that PDA; balances the holder received
+/// by transfer sit in an account they own and are burned by the holder.
+pub fn burn_from_holder<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    from: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    user: AccountInfo<'info>,
+    user_state: AccountInfo<'info>,
+    user_state_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if from.owner == user.key() {
+        return token_interface::burn(
+            CpiContext::new(
+                token_program,
+                token_interface::Burn {
+                    mint,
+                    from: from.to_account_info(),
+                    authority: user,
+                },
+            ),
+            amount
+        );
+    }
+
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            token_program,
+            token_interface::Burn {
+                mint: mint.clone(),
+                from: from.to_account_info(),
+                authority: user_state,
+            },
+            &[&[
+                USER_STATE_SEED,
+                user.key().as_ref(),
+                mint.key().as_ref(),
+                &[user_state_bump],
+            ]]
+        ),
+        amount
+    )
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::states::{factory_state::FactoryState, symbol_registry::SymbolRecord};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// ReleaseSymbol frees a symbol previously reserved by the factory admin.
+/// Symbols claimed by a live stablecoin cannot be released here.
+#[derive(Accounts)]
+pub struct ReleaseSymbol<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [SYMBOL_REGISTRY_SEED, factory_state.key().as_ref(), symbol_record.symbol.as_bytes()],
+        bump = symbol_record.bump,
+        constraint = symbol_record.is_reserved @ StablecoinError::SymbolNotReserved
+    )]
+    pub symbol_record: Account<'info, SymbolRecord>,
+}
+
+pub fn release_symbol(
+    ctx: Context<ReleaseSymbol>,
+) -> Result<()> {
+    emit!(SymbolReleased {
+        admin: ctx.accounts.admin.key(),
+        symbol: ctx.accounts.symbol_record.symbol.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
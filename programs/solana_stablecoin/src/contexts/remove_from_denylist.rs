@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::states::{stablecoin::StablecoinState, denylist::DenylistEntry};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// RemoveFromDenylist lifts a denial by closing the address's entry
+#[derive(Accounts)]
+pub struct RemoveFromDenylist<'info> {
+    #[account(mut)]
+    pub officer: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_state.can_enforce_compliance(&officer.key()) @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        mut,
+        close = officer,
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), denylist_entry.address.as_ref()],
+        bump = denylist_entry.bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+}
+
+pub fn remove_from_denylist(ctx: Context<RemoveFromDenylist>) -> Result<()> {
+    emit!(AddressRemovedFromDenylist {
+        stablecoin: ctx.accounts.stablecoin_state.key(),
+        address: ctx.accounts.denylist_entry.address,
+        by: ctx.accounts.officer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::states::{stablecoin::StablecoinState, minter_allowlist::MinterAllowlistEntry};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// RemoveMinter revokes a minter's approval by closing its allowlist entry
+#[derive(Accounts)]
+pub struct RemoveMinter<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_state.authority == authority.key() @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [MINTER_ALLOWLIST_SEED, stablecoin_state.key().as_ref(), allowlist_entry.minter.as_ref()],
+        bump = allowlist_entry.bump
+    )]
+    pub allowlist_entry: Account<'info, MinterAllowlistEntry>,
+}
+
+pub fn remove_minter(ctx: Context<RemoveMinter>) -> Result<()> {
+    emit!(MinterRemoved {
+        stablecoin: ctx.accounts.stablecoin_state.key(),
+        minter: ctx.accounts.allowlist_entry.minter,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, TokenInterface},
+};
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState, user::UserState};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::contexts::accrue_stability_fee::collect_stability_fee;
+
+/// RepayDebt burns the payer's stablecoins against any position's debt without
+/// releasing collateral. Stability fees grow a position's debt past what its
+/// owner minted, so this is how that debt gets paid down with stablecoins held
+/// elsewhere; the owner then exits through burn_tokens or withdraw_collateral.
+#[derive(Accounts)]
+pub struct RepayDebt<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        seeds = [
+            USER_STATE_SEED,
+            payer.key().as_ref(),
+            stablecoin_state.mint.as_ref()
+        ],
+        bump = payer_state.bump
+    )]
+    pub payer_state: Box<Account<'info, UserState>>,
+
+    /// Payer's stablecoin token account to burn from
+    #[account(
+        mut,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = payer_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub payer_stablecoin_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        address = stablecoin_state.mint
+    )]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: The payer's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    // Programs
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RepayDebt<'info> {
+    pub fn validate(&self, owner: &Pubkey, amount: u64) -> Result<()> {
+        require!(amount > 0, StablecoinError::InvalidRepayAmount);
+
+        require!(
+            self.denylist_entry.data_is_empty(),
+            StablecoinError::AddressDenylisted
+        );
+
+        // Checked against accrued debt, so call after collecting the stability fee
+        let share = self.stablecoin_state.user_shares
+            .iter()
+            .find(|share| share.owner == *owner)
+            .ok_or(StablecoinError::NoUserPosition)?;
+
+        require!(
+            share.mint_amount >= amount,
+            StablecoinError::InvalidRepayAmount
+        );
+
+        require!(
+            self.payer_stablecoin_account.amount >= amount,
+            StablecoinError::InsufficientStablecoinBalance
+        );
+
+        Ok(())
+    }
+}
+
+pub fn repay_debt(
+    ctx: Context<RepayDebt>,
+    owner: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    // 1. Bring every position's stability fee up to date
+    collect_stability_fee(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        ctx.accounts.stability_fee_vault.to_account_info(),
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+    )?;
+
+    // 2. Validate against the accrued debt
+    ctx.accounts.validate(&owner, amount)?;
+
+    // 3. Burn the payer's stablecoins
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.stablecoin_mint.to_account_info(),
+                from: ctx.accounts.payer_stablecoin_account.to_account_info(),
+                authority: ctx.accounts.payer_state.to_account_info(),
+            },
+            &[&[
+                USER_STATE_SEED,
+                ctx.accounts.payer.key().as_ref(),
+                ctx.accounts.stablecoin_mint.key().as_ref(),
+                &[ctx.accounts.payer_state.bump],
+            ]]
+        ),
+        amount
+    )?;
+
+    // 4. Reduce the position's debt and the supply
+    let timestamp = Clock::get()?.unix_timestamp;
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let share = stablecoin.user_shares
+        .iter_mut()
+        .find(|share| share.owner == owner)
+        .ok_or(StablecoinError::NoUserPosition)?;
+
+    share.mint_amount = share.mint_amount
+        .checked_sub(amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+    share.timestamp = timestamp;
+    let remaining_debt = share.mint_amount;
+
+    stablecoin.total_supply = stablecoin.total_supply
+        .checked_sub(amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+    stablecoin.last_updated = timestamp;
+
+    emit!(DebtRepaid {
+        stablecoin: stablecoin.key(),
+        payer: ctx.accounts.payer.key(),
+        owner,
+        amount,
+        remaining_debt,
+        timestamp,
+    });
+
+    Ok(())
+}
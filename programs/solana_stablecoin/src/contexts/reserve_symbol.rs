@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::states::{factory_state::FactoryState, symbol_registry::SymbolRecord};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// ReserveSymbol lets the factory admin hold back a symbol so that no
+/// creator can issue a stablecoin under it.
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ReserveSymbol<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + SymbolRecord::INIT_SPACE,
+        seeds = [SYMBOL_REGISTRY_SEED, factory_state.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_record: Account<'info, SymbolRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn reserve_symbol(
+    ctx: Context<ReserveSymbol>,
+    symbol: String,
+) -> Result<()> {
+    SymbolRecord::validate_symbol(&symbol)?;
+
+    let record = &mut ctx.accounts.symbol_record;
+    record.symbol = symbol.clone();
+    record.stablecoin = Pubkey::default();
+    record.is_reserved = true;
+    record.bump = ctx.bumps.symbol_record;
+
+    emit!(SymbolReserved {
+        admin: ctx.accounts.admin.key(),
+        symbol,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -4,23 +4,26 @@ use crate::errors::StablecoinError;
 use crate::constants::*;
 use crate::events::*;
 
+/// ResumeStablecoin lifts the pause held by the signer's path only. A coin
+/// paused by both the guardian and its authority stays paused until both resume.
 #[derive(Accounts)]
 pub struct ResumeStablecoin<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
     #[account(
-        mut,
-        seeds = [FACTORY_STATE_SEED],
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
         bump = factory_state.bump,
-        constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused,
-        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
     #[account(
         mut,
-        constraint = stablecoin_state.creator == admin.key() @ StablecoinError::Unauthorized,
+        constraint = factory_state.guardian == admin.key()
+            || stablecoin_state.can_pause(&admin.key()) @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
     )]
     pub stablecoin_state: Box<Account<'info, StablecoinState>>,
 
@@ -30,19 +33,27 @@ pub struct ResumeStablecoin<'info> {
 pub fn resume_stablecoin(
     ctx: Context<ResumeStablecoin>,
 ) -> Result<()> {
-    // Undo pause state
+    let by_factory = ctx.accounts.factory_state.guardian == ctx.accounts.admin.key();
+
+    // Undo pause state for this path
     let stablecoin = &mut ctx.accounts.stablecoin_state;
-    require!(stablecoin.is_paused, StablecoinError::NotPaused);
-    
-    stablecoin.is_paused = false;
+    if by_factory {
+        require!(stablecoin.paused_by_factory, StablecoinError::NotPaused);
+        stablecoin.paused_by_factory = false;
+    } else {
+        require!(stablecoin.paused_by_authority, StablecoinError::NotPaused);
+        stablecoin.paused_by_authority = false;
+    }
     stablecoin.last_updated = Clock::get()?.unix_timestamp;
 
     // Emit event
     emit!(StablecoinResumed {
         admin: ctx.accounts.admin.key(),
         stablecoin: ctx.accounts.stablecoin_state.key(),
+        by_factory,
+        is_paused: ctx.accounts.stablecoin_state.is_paused(),
         timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
-}
\ No newline at end of file
+}
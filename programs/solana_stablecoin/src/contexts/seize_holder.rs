@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::extension::permanent_delegate::PermanentDelegate,
+    token_interface::{self, get_mint_extension_data, Mint, TokenAccount, TokenInterface},
+};
+use crate::states::stablecoin::StablecoinState;
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// SeizeHolder moves stablecoins out of a holder's token account using the
+/// Token-2022 permanent delegate, which the stablecoin PDA holds when the mint
+/// was created with `permanent_delegate`. Requires compliance mode. A frozen
+/// account is thawed for the transfer and frozen again afterwards.
+#[derive(Accounts)]
+pub struct SeizeHolder<'info> {
+    pub officer: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_state.can_enforce_compliance(&officer.key()) @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.compliance_mode @ StablecoinError::ComplianceModeDisabled,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(address = stablecoin_state.mint)]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = stablecoin_mint,
+        token::token_program = stablecoin_token_program,
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receives the seized stablecoins
+    #[account(
+        mut,
+        token::mint = stablecoin_mint,
+        token::token_program = stablecoin_token_program,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SeizeHolder<'info> {
+    pub fn validate(&self, amount: u64) -> Result<()> {
+        // 1. The stablecoin PDA must be the mint's permanent delegate
+        let extension = get_mint_extension_data::<PermanentDelegate>(&self.stablecoin_mint.to_account_info())
+            .map_err(|_| StablecoinError::PermanentDelegateDisabled)?;
+        require!(
+            Option::<Pubkey>::from(extension.delegate) == Some(self.stablecoin_state.key()),
+            StablecoinError::PermanentDelegateDisabled
+        );
+
+        // 2. Check the amount against the holder's balance
+        require!(amount > 0, StablecoinError::InvalidSeizureAmount);
+        require!(
+            self.holder_token_account.amount >= amount,
+            StablecoinError::InsufficientStablecoinBalance
+        );
+
+        Ok(())
+    }
+}
+
+pub fn seize_holder<'info>(
+    ctx: Context<'_, '_, '_, 'info, SeizeHolder<'info>>,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts.validate(amount)?;
+
+    let stablecoin_seeds: &[&[u8]] = &[
+        STABLECOIN_SEED,
+        ctx.accounts.stablecoin_state.creator.as_ref(),
+        ctx.accounts.stablecoin_state.original_symbol.as_bytes(),
+        &[ctx.accounts.stablecoin_state.bump],
+    ];
+    let was_frozen = ctx.accounts.holder_token_account.is_frozen();
+
+    // 1. Frozen accounts can't send, so lift the freeze for the transfer
+    if was_frozen {
+        token_interface::thaw_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.stablecoin_token_program.to_account_info(),
+                token_interface::ThawAccount {
+                    account: ctx.accounts.holder_token_account.to_account_info(),
+                    mint: ctx.accounts.stablecoin_mint.to_account_info(),
+                    authority: ctx.accounts.stablecoin_state.to_account_info(),
+                },
+                &[stablecoin_seeds]
+            )
+        )?;
+    }
+
+    // 2. Transfer as permanent delegate, forwarding any transfer hook accounts
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                mint: ctx.accounts.stablecoin_mint.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ctx.accounts.stablecoin_state.to_account_info(),
+            },
+            &[stablecoin_seeds]
+        ).with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        amount,
+        ctx.accounts.stablecoin_mint.decimals,
+    )?;
+
+    // 3. Restore the freeze
+    if was_frozen {
+        token_interface::freeze_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.stablecoin_token_program.to_account_info(),
+                token_interface::FreezeAccount {
+                    account: ctx.accounts.holder_token_account.to_account_info(),
+                    mint: ctx.accounts.stablecoin_mint.to_account_info(),
+                    authority: ctx.accounts.stablecoin_state.to_account_info(),
+                },
+                &[stablecoin_seeds]
+            )
+        )?;
+    }
+
+    emit!(HolderSeized {
+        stablecoin: ctx.accounts.stablecoin_state.key(),
+        holder: ctx.accounts.holder_token_account.owner,
+        token_account: ctx.accounts.holder_token_account.key(),
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        by: ctx.accounts.officer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
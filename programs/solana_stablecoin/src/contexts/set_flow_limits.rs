@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState, flow_limits::FlowLimits};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// SetFlowLimits caps how much of a stablecoin can be minted or redeemed per
+/// window, in total and per holder. Only the factory admin can change them.
+#[derive(Accounts)]
+pub struct SetFlowLimits<'info> {
+    #[account(
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+}
+
+impl<'info> SetFlowLimits<'info> {
+    pub fn validate(&self, limits: &FlowLimits) -> Result<()> {
+        // A zero window turns flow limits off
+        require!(
+            limits.window_duration == 0
+                || (MIN_FLOW_WINDOW..=MAX_FLOW_WINDOW).contains(&limits.window_duration),
+            StablecoinError::InvalidFlowLimits
+        );
+
+        // A holder's cap can't be looser than the stablecoin-wide one
+        for (user_max, max) in [
+            (limits.user_max_net_mint, limits.max_net_mint),
+            (limits.user_max_net_redeem, limits.max_net_redeem),
+        ] {
+            require!(
+                user_max == 0 || max == 0 || user_max <= max,
+                StablecoinError::InvalidFlowLimits
+            );
+        }
+
+        Ok(())
+    }
+}
+
+pub fn set_flow_limits(
+    ctx: Context<SetFlowLimits>,
+    limits: FlowLimits,
+) -> Result<()> {
+    ctx.accounts.validate(&limits)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let old_limits = stablecoin.flow_limits.clone();
+
+    stablecoin.flow_limits = limits.clone();
+    stablecoin.last_updated = timestamp;
+
+    emit!(FlowLimitsUpdated {
+        stablecoin: stablecoin.key(),
+        admin: ctx.accounts.admin.key(),
+        old_limits,
+        new_limits: limits,
+        timestamp,
+    });
+
+    Ok(())
+}
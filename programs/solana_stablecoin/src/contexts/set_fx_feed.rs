@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+use crate::states::{factory_state::FactoryState, fx_feed::FxFeed};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::oracle::is_supported_currency;
+
+/// SetFxFeed registers or replaces the factory's FX feed for a currency pair.
+/// New stablecoins that convert between the pair must use this feed.
+#[derive(Accounts)]
+#[instruction(base_currency: String, quote_currency: String)]
+pub struct SetFxFeed<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FxFeed::INIT_SPACE,
+        seeds = [
+            FX_FEED_SEED,
+            factory_state.key().as_ref(),
+            base_currency.as_bytes(),
+            quote_currency.as_bytes()
+        ],
+        bump
+    )]
+    pub fx_feed: Account<'info, FxFeed>,
+
+    /// Switchboard aggregator quoting quote_currency per unit of base_currency
+    pub aggregator: AccountLoader<'info, AggregatorAccountData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_fx_feed(
+    ctx: Context<SetFxFeed>,
+    base_currency: String,
+    quote_currency: String,
+) -> Result<()> {
+    require!(
+        is_supported_currency(&base_currency) && is_supported_currency(&quote_currency),
+        StablecoinError::InvalidTargetCurrency
+    );
+    require!(base_currency != quote_currency, StablecoinError::InvalidOracleConfig);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let fx_feed = &mut ctx.accounts.fx_feed;
+    let old_aggregator = (fx_feed.aggregator != Pubkey::default()).then_some(fx_feed.aggregator);
+
+    fx_feed.factory = ctx.accounts.factory_state.key();
+    fx_feed.base_currency = base_currency.clone();
+    fx_feed.quote_currency = quote_currency.clone();
+    fx_feed.aggregator = ctx.accounts.aggregator.key();
+    fx_feed.updated_by = ctx.accounts.admin.key();
+    fx_feed.updated_at = timestamp;
+    fx_feed.bump = ctx.bumps.fx_feed;
+
+    emit!(FxFeedSet {
+        factory: ctx.accounts.factory_state.key(),
+        base_currency,
+        quote_currency,
+        old_aggregator,
+        new_aggregator: ctx.accounts.aggregator.key(),
+        admin: ctx.accounts.admin.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
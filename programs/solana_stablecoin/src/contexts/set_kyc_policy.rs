@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::states::{factory_state::FactoryState, stablecoin::{KycPolicy, KycRequirements, StablecoinState}};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::kyc::is_valid_jurisdiction;
+
+/// SetKycPolicy decides whether a stablecoin's holders must pass Etherfuse KYC,
+/// and the minimum tier and allowed jurisdictions they must be attested at when
+/// they do. Only the factory admin can waive KYC for a coin.
+#[derive(Accounts)]
+pub struct SetKycPolicy<'info> {
+    #[account(
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+}
+
+pub fn set_kyc_policy(
+    ctx: Context<SetKycPolicy>,
+    policy: KycPolicy,
+    requirements: KycRequirements,
+) -> Result<()> {
+    require!(
+        policy == KycPolicy::Required || !requirements.needs_attestation(),
+        StablecoinError::InvalidKycRequirements
+    );
+    require!(
+        requirements.jurisdictions
+            .iter()
+            .all(|code| *code == [0; 2] || is_valid_jurisdiction(code)),
+        StablecoinError::InvalidJurisdiction
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let old_policy = stablecoin.kyc_policy;
+    let old_requirements = stablecoin.kyc_requirements;
+
+    stablecoin.kyc_policy = policy;
+    stablecoin.kyc_requirements = requirements;
+    stablecoin.last_updated = timestamp;
+
+    emit!(KycPolicyUpdated {
+        stablecoin: stablecoin.key(),
+        admin: ctx.accounts.admin.key(),
+        old_policy,
+        new_policy: policy,
+        old_requirements,
+        new_requirements: requirements,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::states::stablecoin::{MintPolicy, StablecoinState};
+use crate::errors::StablecoinError;
+use crate::events::*;
+
+/// SetMintPolicy chooses who may mint the stablecoin. Only the stablecoin
+/// authority can change it; redemption is unaffected.
+#[derive(Accounts)]
+pub struct SetMintPolicy<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.authority == authority.key() @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+}
+
+pub fn set_mint_policy(
+    ctx: Context<SetMintPolicy>,
+    policy: MintPolicy,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let old_policy = stablecoin.mint_policy;
+
+    stablecoin.mint_policy = policy;
+    stablecoin.last_updated = timestamp;
+
+    emit!(MintPolicyUpdated {
+        stablecoin: stablecoin.key(),
+        authority: ctx.accounts.authority.key(),
+        old_policy,
+        new_policy: policy,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, TokenInterface},
+};
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::contexts::accrue_stability_fee::settle_stability_fee;
+
+/// SetStabilityFee changes a stablecoin's annual stability fee. Fees owed at
+/// the old rate are accrued through the change first, including time shorter
+/// than the accrual interval. Only the factory admin can change it.
+#[derive(Accounts)]
+pub struct SetStabilityFee<'info> {
+    #[account(
+        mut,
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        address = stablecoin_state.mint
+    )]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_stability_fee(
+    ctx: Context<SetStabilityFee>,
+    rate_bps: u16,
+) -> Result<()> {
+    require!(
+        rate_bps <= MAX_STABILITY_FEE_BPS,
+        StablecoinError::InvalidStabilityFee
+    );
+
+    // Settle what accrued under the old rate
+    settle_stability_fee(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        ctx.accounts.stability_fee_vault.to_account_info(),
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let old_rate_bps = stablecoin.stability_fee_bps;
+
+    stablecoin.stability_fee_bps = rate_bps;
+    stablecoin.last_updated = timestamp;
+
+    emit!(StabilityFeeUpdated {
+        stablecoin: stablecoin.key(),
+        admin: ctx.accounts.admin.key(),
+        old_rate_bps,
+        new_rate_bps: rate_bps,
+        timestamp,
+    });
+
+    Ok(())
+}
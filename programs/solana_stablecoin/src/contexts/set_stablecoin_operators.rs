@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::states::stablecoin::StablecoinState;
+use crate::errors::StablecoinError;
+use crate::events::*;
+
+/// SetStablecoinOperators assigns or clears the delegated metadata editor,
+/// pauser and compliance officer. Only the stablecoin authority can change operators.
+#[derive(Accounts)]
+pub struct SetStablecoinOperators<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.authority == authority.key() @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+}
+
+pub fn set_stablecoin_operators(
+    ctx: Context<SetStablecoinOperators>,
+    metadata_editor: Option<Pubkey>,
+    pauser: Option<Pubkey>,
+    compliance_officer: Option<Pubkey>,
+) -> Result<()> {
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    stablecoin.metadata_editor = metadata_editor;
+    stablecoin.pauser = pauser;
+    stablecoin.compliance_officer = compliance_officer;
+    stablecoin.last_updated = Clock::get()?.unix_timestamp;
+
+    emit!(StablecoinOperatorsUpdated {
+        stablecoin: stablecoin.key(),
+        authority: ctx.accounts.authority.key(),
+        metadata_editor,
+        pauser,
+        compliance_officer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState, protocol_params::{ProtocolParam, StablecoinParamOverrides}};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// SetStablecoinParamOverrides sets or clears a stablecoin's overrides of the
+/// factory protocol params. Only the factory admin can loosen a coin's risk settings.
+#[derive(Accounts)]
+pub struct SetStablecoinParamOverrides<'info> {
+    #[account(
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+}
+
+pub fn set_stablecoin_param_overrides(
+    ctx: Context<SetStablecoinParamOverrides>,
+    overrides: StablecoinParamOverrides,
+) -> Result<()> {
+    if let Some(threshold) = overrides.oracle_staleness_threshold {
+        ProtocolParam::OracleStalenessThreshold.validate(threshold)?;
+    }
+    if let Some(fee) = overrides.redemption_fee_bps {
+        ProtocolParam::RedemptionFeeBps.validate(fee as i64)?;
+    }
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let stablecoin_key = ctx.accounts.stablecoin_state.key();
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let current = stablecoin.param_overrides.clone();
+
+    if current.oracle_staleness_threshold != overrides.oracle_staleness_threshold {
+        emit!(ProtocolParamUpdated {
+            admin: ctx.accounts.admin.key(),
+            stablecoin: Some(stablecoin_key),
+            param: ProtocolParam::OracleStalenessThreshold,
+            old_value: current.oracle_staleness_threshold,
+            new_value: overrides.oracle_staleness_threshold,
+            timestamp,
+        });
+    }
+
+    if current.redemption_fee_bps != overrides.redemption_fee_bps {
+        emit!(ProtocolParamUpdated {
+            admin: ctx.accounts.admin.key(),
+            stablecoin: Some(stablecoin_key),
+            param: ProtocolParam::RedemptionFeeBps,
+            old_value: current.redemption_fee_bps.map(i64::from),
+            new_value: overrides.redemption_fee_bps.map(i64::from),
+            timestamp,
+        });
+    }
+
+    stablecoin.param_overrides = overrides;
+    stablecoin.last_updated = timestamp;
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use crate::states::stablecoin::StablecoinState;
+use anchor_spl::{
+    token::{self, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
+};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::math::{mul_div_u64, Rounding};
+use crate::contexts::redeem::burn_from_holder;
+
+/// SettleStablecoin lets any holder of a shut-down stablecoin burn it for a
+/// pro-rata share of the collateral vault at the frozen settlement rate,
+/// regardless of whether they hold a position.
+#[derive(Accounts)]
+pub struct SettleStablecoin<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.is_shutdown @ StablecoinError::StablecoinNotShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    /// CHECK: The user's state PDA, which signs for balances it holds and need not exist
+    #[account(
+        seeds = [
+            USER_STATE_SEED,
+            user.key().as_ref(),
+            stablecoin_state.mint.as_ref()
+        ],
+        bump
+    )]
+    pub user_state: UncheckedAccount<'info>,
+
+    /// User's stablebond token account to receive bonds
+    #[account(
+        mut,
+        constraint = user_bond_account.owner == user.key() @ StablecoinError::InvalidTokenAccountOwner,
+        constraint = user_bond_account.mint == stablecoin_state.bond_mint @ StablecoinError::InvalidBondMint
+    )]
+    pub user_bond_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's stablecoin token account to burn from, held by the user or by their user state
+    #[account(
+        mut,
+        token::mint = stablecoin_mint,
+        token::token_program = stablecoin_token_program,
+        constraint = user_stablecoin_account.owner == user.key()
+            || user_stablecoin_account.owner == user_state.key() @ StablecoinError::InvalidTokenAccountOwner
+    )]
+    pub user_stablecoin_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        address = stablecoin_state.mint
+    )]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Collateral vault
+    #[account(
+        mut,
+        address = stablecoin_state.collateral_vault
+    )]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The user's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    // Programs
+    pub token_program: Program<'info, Token>,
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SettleStablecoin<'info> {
+    pub fn validate(&self, amount: u64) -> Result<()> {
+        require!(amount > 0, StablecoinError::RedeemAmountTooSmall);
+
+        // Denylisted holders stay frozen out through the wind-down
+        require!(
+            self.denylist_entry.data_is_empty(),
+            StablecoinError::AddressDenylisted
+        );
+        require!(
+            self.user_stablecoin_account.amount >= amount,
+            StablecoinError::InsufficientStablecoinBalance
+        );
+        Ok(())
+    }
+
+    /// Bonds owed for `stablecoin_amount` at the frozen settlement rate. The
+    /// payout is in bonds, so the frozen settlement price doesn't enter it; the
+    /// price is kept as the record of what the bonds were worth at shutdown.
+    pub fn calculate_settlement_amount(&self, stablecoin_amount: u64) -> Result<u64> {
+        let bond_amount = mul_div_u64(
+            stablecoin_amount,
+            self.stablecoin_state.settlement_rate,
+            PRICE_SCALE as u64,
+            Rounding::Down,
+        )?;
+
+        // The rate rounds down, so payouts never outrun the vault and it keeps
+        // the dust once every holder has settled. The cap only guards against
+        // the vault holding less than total_collateral.
+        Ok(bond_amount.min(self.collateral_vault.amount))
+    }
+}
+
+pub fn settle_stablecoin(
+    ctx: Context<SettleStablecoin>,
+    stablecoin_amount: u64,
+) -> Result<()> {
+    ctx.accounts.validate(stablecoin_amount)?;
+
+    let bond_amount = ctx.accounts.calculate_settlement_amount(stablecoin_amount)?;
+    msg!("Settling {} stablecoins for {} bonds", stablecoin_amount, bond_amount);
+
+    // 1. Burn stablecoins from user
+    burn_from_holder(
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        &ctx.accounts.user_stablecoin_account,
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.user_state.to_account_info(),
+        ctx.bumps.user_state,
+        stablecoin_amount,
+    )?;
+
+    // 2. Transfer the pro-rata share of bonds from vault to user
+    if bond_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    to: ctx.accounts.user_bond_account.to_account_info(),
+                    authority: ctx.accounts.stablecoin_state.to_account_info(),
+                },
+                &[&[
+                    STABLECOIN_SEED,
+                    ctx.accounts.stablecoin_state.creator.as_ref(),
+                    ctx.accounts.stablecoin_state.original_symbol.as_bytes(),
+                    &[ctx.accounts.stablecoin_state.bump],
+                ]]
+            ),
+            bond_amount
+        )?;
+    }
+
+    // 3. Update state
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    stablecoin.total_supply = stablecoin.total_supply
+        .checked_sub(stablecoin_amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+
+    stablecoin.total_collateral = stablecoin.total_collateral
+        .saturating_sub(bond_amount);
+
+    stablecoin.last_updated = Clock::get()?.unix_timestamp;
+
+    emit!(StablecoinSettled {
+        user: ctx.accounts.user.key(),
+        mint: ctx.accounts.stablecoin_mint.key(),
+        stablecoin_amount,
+        bond_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
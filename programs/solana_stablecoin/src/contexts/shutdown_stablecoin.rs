@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, TokenInterface},
+};
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::math::{mul_div_u64, Rounding};
+use crate::oracle::scaled_price;
+use crate::contexts::accrue_stability_fee::settle_stability_fee;
+
+/// ShutdownStablecoin permanently winds a stablecoin down, e.g. after the backing
+/// stablebond defaults. The final bond price and the bonds-per-stablecoin rate are
+/// frozen, minting is disabled for good, and holders exit via settle_stablecoin.
+/// Outstanding stability fees are accrued first so the fee vault's tokens share
+/// in the settlement once withdrawn with withdraw_stability_fees.
+#[derive(Accounts)]
+pub struct ShutdownStablecoin<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        mut,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        address = stablecoin_state.mint
+    )]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    // Switchboard oracle accounts
+    pub oracle: AccountLoader<'info, AggregatorAccountData>,
+
+    // Programs
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ShutdownStablecoin<'info> {
+    /// Last confirmed bond price. Staleness is deliberately not enforced since
+    /// the feed may have stopped updating by the time a shutdown is needed.
+    pub fn get_final_price(&self) -> Result<u64> {
+        scaled_price(self.oracle.load()?.latest_confirmed_round.result)
+    }
+
+    /// Bonds released per stablecoin, scaled by PRICE_SCALE. Rounding down means
+    /// burning the full supply drains the collateral vault up to a little dust.
+    pub fn calculate_settlement_rate(&self) -> Result<u64> {
+        let stablecoin = &self.stablecoin_state;
+        if stablecoin.total_supply == 0 {
+            return Ok(0);
+        }
+
+        mul_div_u64(
+            stablecoin.total_collateral,
+            PRICE_SCALE as u64,
+            stablecoin.total_supply,
+            Rounding::Down,
+        )
+    }
+}
+
+pub fn shutdown_stablecoin(
+    ctx: Context<ShutdownStablecoin>,
+) -> Result<()> {
+    // Fees stop accruing at shutdown, so settle what is owed up to now
+    settle_stability_fee(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        ctx.accounts.stability_fee_vault.to_account_info(),
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+    )?;
+
+    let settlement_price = ctx.accounts.get_final_price()?;
+    let settlement_rate = ctx.accounts.calculate_settlement_rate()?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    stablecoin.is_shutdown = true;
+    stablecoin.settlement_price = settlement_price;
+    stablecoin.settlement_rate = settlement_rate;
+    stablecoin.shutdown_at = current_timestamp;
+    stablecoin.last_updated = current_timestamp;
+
+    emit!(StablecoinShutdown {
+        admin: ctx.accounts.admin.key(),
+        stablecoin: stablecoin.key(),
+        settlement_price,
+        settlement_rate,
+        total_supply: stablecoin.total_supply,
+        total_collateral: stablecoin.total_collateral,
+        timestamp: current_timestamp,
+    });
+
+    Ok(())
+}
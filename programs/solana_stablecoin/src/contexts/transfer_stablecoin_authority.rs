@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::states::stablecoin::StablecoinState;
+use crate::errors::StablecoinError;
+use crate::events::*;
+
+/// TransferStablecoinAuthority proposes a new authority for the stablecoin.
+/// The transfer completes only once the proposed key accepts it.
+#[derive(Accounts)]
+pub struct TransferStablecoinAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_state.authority == authority.key() @ StablecoinError::Unauthorized,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+}
+
+pub fn transfer_stablecoin_authority(
+    ctx: Context<TransferStablecoinAuthority>,
+    new_authority: Option<Pubkey>,
+) -> Result<()> {
+    // Passing None cancels any pending transfer
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    stablecoin.pending_authority = new_authority;
+    stablecoin.last_updated = Clock::get()?.unix_timestamp;
+
+    emit!(StablecoinAuthorityTransferStarted {
+        stablecoin: stablecoin.key(),
+        authority: ctx.accounts.authority.key(),
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -26,13 +26,14 @@ pub struct UpdateFactoryConfig<'info> {
     /// The factory state PDA containing configuration
     #[account(
         mut,
-        seeds = [FACTORY_STATE_SEED],
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
         bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
     #[account(
-        address = USDC_MINT @ StablecoinError::InvalidUSDCMint
+        address = factory_state.fee_mint @ StablecoinError::InvalidFeeMint
     )]
     pub fee_mint: Account<'info, Mint>,
 
@@ -88,6 +89,7 @@ pub fn update_factory_config(
     new_min_collateral_ratio: Option<u16>,
     new_base_fee_rate: Option<u16>,
     new_fee_vault: Option<Pubkey>,
+    new_guardian: Option<Pubkey>,
 ) -> Result<()> {
     // Validate input parameters if provided
     ctx.accounts.validate(new_min_collateral_ratio, new_base_fee_rate)?;
@@ -98,7 +100,7 @@ pub fn update_factory_config(
     if let Some(collector_account) = &ctx.accounts.new_collector {
         let collector_key = collector_account.key();
         if !factory_state.authorized_collectors.contains(&collector_key) 
-            && factory_state.authorized_collectors.len() < MAX_ALLOWED_COLLECTORS 
+            && factory_state.authorized_collectors.len() < factory_state.protocol_params.max_allowed_collectors as usize
         {
             factory_state.authorized_collectors.push(collector_key);
         }
@@ -109,6 +111,11 @@ pub fn update_factory_config(
         factory_state.admin = new_admin;
     }
 
+    // Update guardian if provided
+    if let Some(guardian) = new_guardian {
+        factory_state.guardian = guardian;
+    }
+
     // Update collateral ratio if provided
     if let Some(ratio) = new_min_collateral_ratio {
         factory_state.min_collateral_ratio = ratio;
@@ -124,11 +131,10 @@ pub fn update_factory_config(
         factory_state.fee_vault = vault;
     }
 
-    factory_state.protocol_version += 1;
-
     // Emit configuration update event
     emit!(FactoryConfigUpdated {
         admin: factory_state.admin,
+        guardian: factory_state.guardian,
         fee_vault: factory_state.fee_vault,
         min_collateral_ratio: factory_state.min_collateral_ratio,
         base_fee_rate: factory_state.base_fee_rate,
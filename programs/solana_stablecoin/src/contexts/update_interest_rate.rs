@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token_2022, token_interface::{self, TokenInterface}};
+use crate::states::stablecoin::StablecoinState;
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// UpdateInterestRate changes the rate on a Token-2022 interest-bearing stablecoin mint
+/// so that rebase yield is reflected in wallet balances.
+#[derive(Accounts)]
+pub struct UpdateInterestRate<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [
+            STABLECOIN_SEED,
+            stablecoin_state.creator.key().as_ref(),
+            stablecoin_state.original_symbol.as_bytes()
+        ],
+        bump = stablecoin_state.bump,
+        constraint = stablecoin_state.authority == authority.key() @ StablecoinError::UnauthorizedAccess,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        address = stablecoin_state.mint
+    )]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram,
+        constraint = stablecoin_token_program.key() == token_2022::ID @ StablecoinError::InvalidTokenProgram
+    )]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn update_interest_rate(
+    ctx: Context<UpdateInterestRate>,
+    rate: i16,
+) -> Result<()> {
+    token_interface::interest_bearing_mint_update_rate(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::InterestBearingMintUpdateRate {
+                token_program_id: ctx.accounts.stablecoin_token_program.to_account_info(),
+                mint: ctx.accounts.stablecoin_mint.to_account_info(),
+                rate_authority: ctx.accounts.stablecoin_state.to_account_info(),
+            },
+            &[&[
+                STABLECOIN_SEED,
+                ctx.accounts.stablecoin_state.creator.as_ref(),
+                ctx.accounts.stablecoin_state.original_symbol.as_bytes(),
+                &[ctx.accounts.stablecoin_state.bump],
+            ]]
+        ),
+        rate,
+    )?;
+
+    emit!(InterestRateUpdated {
+        authority: ctx.accounts.authority.key(),
+        mint: ctx.accounts.stablecoin_mint.key(),
+        rate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::states::{factory_state::FactoryState, protocol_params::{ProtocolParam, ProtocolParamsUpdate}};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// UpdateProtocolParams changes factory-wide protocol tunables within their
+/// sane bounds. Emits one event per parameter that actually changed.
+#[derive(Accounts)]
+pub struct UpdateProtocolParams<'info> {
+    #[account(
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+}
+
+/// Validates and applies a single parameter change, emitting an event if it differs
+fn apply_param<T>(
+    admin: Pubkey,
+    param: ProtocolParam,
+    current: &mut T,
+    new_value: Option<T>,
+) -> Result<()>
+where
+    T: Copy + PartialEq + Into<i64>,
+{
+    let Some(new_value) = new_value else {
+        return Ok(());
+    };
+
+    param.validate(new_value.into())?;
+    if *current == new_value {
+        return Ok(());
+    }
+
+    let old_value = *current;
+    *current = new_value;
+
+    emit!(ProtocolParamUpdated {
+        admin,
+        stablecoin: None,
+        param,
+        old_value: Some(old_value.into()),
+        new_value: Some(new_value.into()),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn update_protocol_params(
+    ctx: Context<UpdateProtocolParams>,
+    updates: ProtocolParamsUpdate,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    let factory_state = &mut ctx.accounts.factory_state;
+
+    // Shrinking capacity below what is already in use would strand entries
+    if let Some(max_bonds) = updates.max_allowed_bonds {
+        require!(
+            max_bonds as usize >= factory_state.allowed_bond_configs.len(),
+            StablecoinError::InvalidProtocolParam
+        );
+    }
+    if let Some(max_collectors) = updates.max_allowed_collectors {
+        require!(
+            max_collectors as usize >= factory_state.authorized_collectors.len(),
+            StablecoinError::InvalidProtocolParam
+        );
+    }
+
+    let params = &mut factory_state.protocol_params;
+    apply_param(admin, ProtocolParam::OracleStalenessThreshold, &mut params.oracle_staleness_threshold, updates.oracle_staleness_threshold)?;
+    apply_param(admin, ProtocolParam::RateFreshnessThreshold, &mut params.rate_freshness_threshold, updates.rate_freshness_threshold)?;
+    apply_param(admin, ProtocolParam::RateUpdateInterval, &mut params.rate_update_interval, updates.rate_update_interval)?;
+    apply_param(admin, ProtocolParam::RebaseInterval, &mut params.rebase_interval, updates.rebase_interval)?;
+    apply_param(admin, ProtocolParam::ProtocolFeeBps, &mut params.protocol_fee_bps, updates.protocol_fee_bps)?;
+    apply_param(admin, ProtocolParam::RedemptionFeeBps, &mut params.redemption_fee_bps, updates.redemption_fee_bps)?;
+    apply_param(admin, ProtocolParam::MaxAllowedBonds, &mut params.max_allowed_bonds, updates.max_allowed_bonds)?;
+    apply_param(admin, ProtocolParam::MaxAllowedCollectors, &mut params.max_allowed_collectors, updates.max_allowed_collectors)?;
+
+    factory_state.last_update = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
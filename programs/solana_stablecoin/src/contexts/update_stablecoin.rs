@@ -1,19 +1,22 @@
 use anchor_lang::prelude::*;
-use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState};
+use anchor_spl::metadata::{self, Metadata, mpl_token_metadata::types::DataV2};
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState, symbol_registry::SymbolRecord};
 use crate::errors::StablecoinError;
 use crate::constants::*;
 use crate::events::*;
 
 #[derive(Accounts)]
+#[instruction(name: Option<String>, symbol: Option<String>)]
 pub struct UpdateStablecoin<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
     /// Factory state PDA
     #[account(
-        seeds = [FACTORY_STATE_SEED],
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
         bump = factory_state.bump,
-        constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused
+        constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
     )]
     pub factory_state: Box<Account<'info, FactoryState>>,
 
@@ -26,10 +29,47 @@ pub struct UpdateStablecoin<'info> {
             stablecoin_state.original_symbol.as_bytes()
         ],
         bump = stablecoin_state.bump,
-        constraint = stablecoin_state.creator == authority.key() @ StablecoinError::UnauthorizedAccess
+        constraint = stablecoin_state.can_edit_metadata(&authority.key()) @ StablecoinError::UnauthorizedAccess,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
     )]
     pub stablecoin_state: Box<Account<'info, StablecoinState>>,
 
+    /// Metaplex metadata PDA for the stablecoin mint
+    /// CHECK: Seeds are verified against the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            stablecoin_state.mint.as_ref()
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Registry claim on the new symbol, required when renaming
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SymbolRecord::INIT_SPACE,
+        seeds = [SYMBOL_REGISTRY_SEED, factory_state.key().as_ref(), symbol.as_deref().unwrap_or_default().as_bytes()],
+        bump
+    )]
+    pub new_symbol_record: Option<Box<Account<'info, SymbolRecord>>>,
+
+    /// Registry claim on the current symbol, released when renaming
+    #[account(
+        mut,
+        close = authority,
+        seeds = [SYMBOL_REGISTRY_SEED, factory_state.key().as_ref(), stablecoin_state.symbol.as_bytes()],
+        bump = old_symbol_record.bump,
+        constraint = old_symbol_record.stablecoin == stablecoin_state.key() @ StablecoinError::UnauthorizedAccess
+    )]
+    pub old_symbol_record: Option<Box<Account<'info, SymbolRecord>>>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
 }
 
@@ -38,15 +78,37 @@ impl<'info> UpdateStablecoin<'info> {
         &self,
         name: &Option<String>,
         symbol: &Option<String>,
+        uri: &Option<String>,
     ) -> Result<()> {
         // Validate name if provided
         if let Some(name) = name {
             require!(!name.is_empty() && name.len() <= 32, StablecoinError::InvalidName);
         }
 
-        // Validate symbol if provided
-        if let Some(symbol) = symbol {
-            require!(!symbol.is_empty() && symbol.len() <= 10, StablecoinError::InvalidSymbol);
+        // Validate symbol if provided; renames move the registry claim atomically
+        let renaming = match symbol {
+            Some(symbol) => {
+                SymbolRecord::validate_symbol(symbol)?;
+                *symbol != self.stablecoin_state.symbol
+            }
+            None => false,
+        };
+
+        if renaming {
+            require!(
+                self.new_symbol_record.is_some() && self.old_symbol_record.is_some(),
+                StablecoinError::SymbolRecordRequired
+            );
+        } else {
+            require!(
+                self.new_symbol_record.is_none() && self.old_symbol_record.is_none(),
+                StablecoinError::UnexpectedSymbolRecord
+            );
+        }
+
+        // Validate uri if provided
+        if let Some(uri) = uri {
+            require!(uri.len() <= MAX_URI_LENGTH, StablecoinError::InvalidUri);
         }
 
         Ok(())
@@ -57,9 +119,18 @@ pub fn update_stablecoin(
     ctx: Context<UpdateStablecoin>,
     name: Option<String>,
     symbol: Option<String>,
+    uri: Option<String>,
 ) -> Result<()> {
-    ctx.accounts.validate(&name, &symbol)?;
+    ctx.accounts.validate(&name, &symbol, &uri)?;
     
+    // Claim the new symbol; the old record is closed on exit
+    if let Some(new_symbol_record) = ctx.accounts.new_symbol_record.as_mut() {
+        new_symbol_record.symbol = symbol.clone().unwrap_or_default();
+        new_symbol_record.stablecoin = ctx.accounts.stablecoin_state.key();
+        new_symbol_record.is_reserved = false;
+        new_symbol_record.bump = ctx.bumps.new_symbol_record.ok_or(StablecoinError::SymbolRecordRequired)?;
+    }
+
     let stablecoin = &mut ctx.accounts.stablecoin_state;
     let current_timestamp = Clock::get()?.unix_timestamp;
     
@@ -73,14 +144,51 @@ pub fn update_stablecoin(
         stablecoin.symbol = new_symbol;
     }
 
+    // Update uri if provided
+    if let Some(new_uri) = uri.clone() {
+        stablecoin.uri = new_uri;
+    }
+
     // Update the last_updated timestamp
     stablecoin.last_updated = current_timestamp;
 
+    // Keep the Metaplex metadata in sync with program state
+    let data = DataV2 {
+        name: stablecoin.name.clone(),
+        symbol: stablecoin.symbol.clone(),
+        uri: stablecoin.uri.clone(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    metadata::update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            metadata::UpdateMetadataAccountsV2 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                update_authority: stablecoin.to_account_info(),
+            },
+            &[&[
+                STABLECOIN_SEED,
+                stablecoin.creator.as_ref(),
+                stablecoin.original_symbol.as_bytes(),
+                &[stablecoin.bump],
+            ]]
+        ),
+        None,
+        Some(data),
+        None,
+        None,
+    )?;
+
     emit!(StablecoinUpdated {
         authority: ctx.accounts.authority.key(),
         mint: stablecoin.mint,
-        name: name,
-        symbol: symbol,
+        name,
+        symbol,
+        uri,
         timestamp: current_timestamp,
     });
 
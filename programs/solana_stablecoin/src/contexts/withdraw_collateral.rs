@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+use crate::{states::{factory_state::FactoryState, stablecoin::StablecoinState, flow_limits::{record_flow, FlowDirection}}, user::UserState};
+use anchor_spl::{
+    associated_token::AssociatedToken, token::{self, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
+};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::oracle::load_bond_price;
+use crate::math::Rounding;
+use crate::contexts::accrue_stability_fee::collect_stability_fee;
+
+/// WithdrawCollateral releases excess stablebonds from an existing position.
+/// The position must remain at or above the stablecoin's collateral ratio afterwards.
+#[derive(Accounts)]
+pub struct WithdrawCollateral<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !stablecoin_state.is_paused() @ StablecoinError::StablecoinPaused,
+        constraint = !stablecoin_state.is_shutdown @ StablecoinError::StablecoinIsShutdown,
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = !factory_state.is_paused @ StablecoinError::FactoryPaused,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            USER_STATE_SEED,
+            user.key().as_ref(),
+            stablecoin_state.mint.as_ref()
+        ],
+        bump = user_state.bump
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    /// User's stablebond token account to receive bonds
+    #[account(
+        mut,
+        constraint = user_bond_account.owner == user.key() @ StablecoinError::InvalidTokenAccountOwner,
+        constraint = user_bond_account.mint == stablecoin_state.bond_mint @ StablecoinError::InvalidBondMint
+    )]
+    pub user_bond_account: Account<'info, TokenAccount>,
+
+    /// Collateral vault
+    #[account(
+        mut,
+        address = stablecoin_state.collateral_vault
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+
+    /// The stablecoin mint, for minting accrued stability fees
+    #[account(
+        mut,
+        address = stablecoin_state.mint
+    )]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: The user's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    // Switchboard oracle accounts
+    pub oracle: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed into the target currency, required when the stablecoin has one
+    pub fx_oracle: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    // Programs
+    pub token_program: Program<'info, Token>,
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawCollateral<'info> {
+    pub fn validate(&self, bond_amount: u64, bond_price: u64) -> Result<()> {
+        msg!("Starting validation for collateral withdrawal: {}", bond_amount);
+
+        require!(bond_amount > 0, StablecoinError::InsufficientCollateral);
+
+        // Denylisted addresses can't pull collateral out either
+        require!(
+            self.denylist_entry.data_is_empty(),
+            StablecoinError::AddressDenylisted
+        );
+
+        // 1. Verify the user has enough collateral in their position
+        let share = self.stablecoin_state.user_shares
+            .iter()
+            .find(|share| share.owner == self.user.key())
+            .ok_or(StablecoinError::NoUserPosition)?;
+
+        require!(
+            share.bond_amount >= bond_amount,
+            StablecoinError::InsufficientUserShare
+        );
+
+        // 2. Verify the remaining collateral still backs the position's debt
+        let remaining = share.bond_amount
+            .checked_sub(bond_amount)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        require!(
+            remaining >= self.stablecoin_state
+                .required_collateral(share.mint_amount, bond_price)?,
+            StablecoinError::CollateralRatioTooLow
+        );
+
+        Ok(())
+    }
+
+    pub fn get_oracle_price(&self) -> Result<u64> {
+        msg!("Fetching oracle price");
+
+        load_bond_price(
+            &self.oracle,
+            &self.stablecoin_state,
+            &self.fx_oracle,
+            self.stablecoin_state.oracle_staleness_threshold(&self.factory_state),
+        )
+    }
+}
+
+pub fn withdraw_collateral(
+    ctx: Context<WithdrawCollateral>,
+    bond_amount: u64,
+) -> Result<()> {
+    // Bring every position's stability fee up to date before checking health
+    collect_stability_fee(
+        &mut ctx.accounts.stablecoin_state,
+        ctx.accounts.stablecoin_mint.to_account_info(),
+        ctx.accounts.stability_fee_vault.to_account_info(),
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+    )?;
+
+    // 1. Validate position health after withdrawal
+    let bond_price = ctx.accounts.get_oracle_price()?;
+    ctx.accounts.validate(bond_amount, bond_price)?;
+
+    // 2. Transfer bonds from vault to user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.collateral_vault.to_account_info(),
+                to: ctx.accounts.user_bond_account.to_account_info(),
+                authority: ctx.accounts.stablecoin_state.to_account_info(),
+            },
+            &[&[
+                STABLECOIN_SEED,
+                ctx.accounts.stablecoin_state.creator.as_ref(),
+                ctx.accounts.stablecoin_state.original_symbol.as_bytes(),
+                &[ctx.accounts.stablecoin_state.bump],
+            ]]
+        ),
+        bond_amount
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let user_key = ctx.accounts.user.key();
+
+    // 3. Update the user's position
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    let index = stablecoin.user_shares
+        .iter()
+        .position(|s| s.owner == user_key)
+        .ok_or(StablecoinError::UserShareNotFound)?;
+
+    let share = &mut stablecoin.user_shares[index];
+    share.bond_amount = share.bond_amount
+        .checked_sub(bond_amount)
+        .ok_or(StablecoinError::InsufficientUserShare)?;
+    share.timestamp = timestamp;
+    let position_collateral = share.bond_amount;
+
+    // If position is completely empty, remove it
+    if share.bond_amount == 0 && share.mint_amount == 0 {
+        stablecoin.user_shares.remove(index);
+    }
+
+    // Count the collateral's face value against the redemption flow limits,
+    // so withdrawals can't drain the vault around them
+    let withdrawn_value = stablecoin.value_of_bonds(bond_amount, bond_price, Rounding::Up)?;
+    let user_state = &mut ctx.accounts.user_state;
+    record_flow(
+        &mut ctx.accounts.stablecoin_state,
+        user_key,
+        &mut user_state.flow_window,
+        FlowDirection::Redeem,
+        withdrawn_value,
+    )?;
+
+    // 4. Update state
+    let stablecoin = &mut ctx.accounts.stablecoin_state;
+    stablecoin.total_collateral = stablecoin.total_collateral
+        .checked_sub(bond_amount)
+        .ok_or(StablecoinError::MathOverflow)?;
+    stablecoin.last_updated = timestamp;
+
+    emit!(CollateralWithdrawn {
+        user: user_key,
+        stablecoin: stablecoin.key(),
+        bond_amount,
+        position_collateral,
+        timestamp,
+    });
+
+    Ok(())
+}
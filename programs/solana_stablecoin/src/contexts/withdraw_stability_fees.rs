@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, TokenInterface},
+};
+use crate::states::{factory_state::FactoryState, stablecoin::StablecoinState, user::UserState};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+
+/// WithdrawStabilityFees moves accrued stability fees out of the factory's fee
+/// vault into a recipient's holder account, creating it if needed. From there
+/// they can repay position debt, be redeemed, or be settled after a shutdown,
+/// which the vault itself can't do. Only the factory admin can withdraw.
+#[derive(Accounts)]
+pub struct WithdrawStabilityFees<'info> {
+    #[account(
+        mut,
+        constraint = factory_state.admin == admin.key() @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [FACTORY_STATE_SEED, factory_state.id_seed().as_ref()],
+        bump = factory_state.bump,
+        constraint = factory_state.is_current() @ StablecoinError::AccountNotMigrated
+    )]
+    pub factory_state: Box<Account<'info, FactoryState>>,
+
+    #[account(
+        constraint = stablecoin_state.is_current() @ StablecoinError::AccountNotMigrated,
+        constraint = stablecoin_state.factory == factory_state.key() @ StablecoinError::InvalidFactory
+    )]
+    pub stablecoin_state: Box<Account<'info, StablecoinState>>,
+
+    /// The stablecoin mint
+    #[account(address = stablecoin_state.mint)]
+    pub stablecoin_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Protocol fee vault for stability fees, owned by factory PDA
+    #[account(
+        mut,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = factory_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub stability_fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: Any address; the fees land in its holder account below
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Recipient's state PDA
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + UserState::INIT_SPACE,
+        seeds = [
+            USER_STATE_SEED,
+            recipient.key().as_ref(),
+            stablecoin_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub recipient_state: Box<Account<'info, UserState>>,
+
+    /// Recipient's stablecoin token account
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = stablecoin_mint,
+        associated_token::authority = recipient_state,
+        associated_token::token_program = stablecoin_token_program,
+    )]
+    pub recipient_stablecoin_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: The recipient's denylist entry PDA, which must not exist
+    #[account(
+        seeds = [DENYLIST_SEED, stablecoin_state.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    // Programs
+    #[account(address = stablecoin_state.token_program @ StablecoinError::InvalidTokenProgram)]
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawStabilityFees<'info> {
+    pub fn validate(&self, amount: u64) -> Result<()> {
+        require!(amount > 0, StablecoinError::InvalidFeeWithdrawal);
+        require!(
+            self.stability_fee_vault.amount >= amount,
+            StablecoinError::InvalidFeeWithdrawal
+        );
+        require!(
+            self.denylist_entry.data_is_empty(),
+            StablecoinError::AddressDenylisted
+        );
+        Ok(())
+    }
+}
+
+pub fn withdraw_stability_fees<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawStabilityFees<'info>>,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts.validate(amount)?;
+
+    // 1. A new holder account needs its seeds recorded, as on a first mint
+    let recipient_state = &mut ctx.accounts.recipient_state;
+    if recipient_state.user == Pubkey::default() {
+        recipient_state.bump = ctx.bumps.recipient_state;
+        recipient_state.user = ctx.accounts.recipient.key();
+        recipient_state.stablecoin = ctx.accounts.stablecoin_mint.key();
+        recipient_state.last_yield_collection = Clock::get()?.unix_timestamp;
+    }
+
+    // 2. Transfer out of the vault as the factory, forwarding any transfer hook accounts
+    let factory_id_seed = ctx.accounts.factory_state.id_seed();
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.stability_fee_vault.to_account_info(),
+                mint: ctx.accounts.stablecoin_mint.to_account_info(),
+                to: ctx.accounts.recipient_stablecoin_account.to_account_info(),
+                authority: ctx.accounts.factory_state.to_account_info(),
+            },
+            &[&[
+                FACTORY_STATE_SEED,
+                factory_id_seed.as_ref(),
+                &[ctx.accounts.factory_state.bump],
+            ]]
+        ).with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        amount,
+        ctx.accounts.stablecoin_mint.decimals,
+    )?;
+
+    emit!(StabilityFeesWithdrawn {
+        stablecoin: ctx.accounts.stablecoin_state.key(),
+        admin: ctx.accounts.admin.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
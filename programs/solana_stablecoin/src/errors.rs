@@ -219,4 +219,115 @@ pub enum StablecoinError {
 
     #[msg("Symbol is invalid")]
     InvalidSymbol,
-}
\ No newline at end of file
+
+    #[msg("Token program does not match the stablecoin mint")]
+    InvalidTokenProgram,
+
+    #[msg("Metadata URI is invalid")]
+    InvalidUri,
+
+    #[msg("Symbol registry accounts are required to change the symbol")]
+    SymbolRecordRequired,
+
+    #[msg("Symbol is not reserved")]
+    SymbolNotReserved,
+
+    #[msg("Symbol registry accounts are only accepted when changing the symbol")]
+    UnexpectedSymbolRecord,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Stablecoin has been shut down")]
+    StablecoinIsShutdown,
+
+    #[msg("Stablecoin has not been shut down")]
+    StablecoinNotShutdown,
+
+    #[msg("Account data does not match the expected layout")]
+    InvalidAccountData,
+
+    #[msg("Account must be migrated to the current schema version")]
+    AccountNotMigrated,
+
+    #[msg("Account is already at the current schema version")]
+    AlreadyMigrated,
+
+    #[msg("Account schema version is not supported")]
+    UnsupportedSchemaVersion,
+
+    #[msg("Protocol parameter is out of bounds")]
+    InvalidProtocolParam,
+
+    #[msg("Mint does not match the factory fee mint")]
+    InvalidFeeMint,
+
+    #[msg("Account does not belong to this factory")]
+    InvalidFactory,
+
+    #[msg("Fee exceeds the maximum accepted by the caller")]
+    FeeExceedsMaximum,
+
+    #[msg("Transaction landed after its expiry")]
+    TransactionExpired,
+
+    #[msg("KYC record is missing or has been revoked")]
+    KycRevoked,
+
+    #[msg("Stablecoin was not created in compliance mode")]
+    ComplianceModeDisabled,
+
+    #[msg("Address is on the stablecoin's denylist")]
+    AddressDenylisted,
+
+    #[msg("Minter is not on the stablecoin's allowlist")]
+    MinterNotAllowlisted,
+
+    #[msg("Net flow limit for the current window exceeded")]
+    FlowLimitExceeded,
+
+    #[msg("Holder's net flow limit for the current window exceeded")]
+    UserFlowLimitExceeded,
+
+    #[msg("Invalid flow limits")]
+    InvalidFlowLimits,
+
+    #[msg("Stability fee is out of bounds")]
+    InvalidStabilityFee,
+
+    #[msg("FX feed is not the one registered for this currency pair")]
+    InvalidFxFeed,
+
+    #[msg("Stablecoin PDA is not the mint's permanent delegate")]
+    PermanentDelegateDisabled,
+
+    #[msg("Seizure amount must be positive")]
+    InvalidSeizureAmount,
+
+    #[msg("Stablecoin requires a KYC attestation for this holder")]
+    MissingKycAttestation,
+
+    #[msg("KYC attestation has expired")]
+    KycAttestationExpired,
+
+    #[msg("KYC tier is below the stablecoin's minimum")]
+    KycTierTooLow,
+
+    #[msg("Holder's jurisdiction is not allowed for this stablecoin")]
+    JurisdictionNotAllowed,
+
+    #[msg("Jurisdiction must be an upper-case ISO 3166-1 alpha-2 code")]
+    InvalidJurisdiction,
+
+    #[msg("KYC requirements only apply when KYC is required")]
+    InvalidKycRequirements,
+
+    #[msg("Symbol is registered to another stablecoin")]
+    SymbolTaken,
+
+    #[msg("Repayment must be positive and no more than the position's debt")]
+    InvalidRepayAmount,
+
+    #[msg("Fee withdrawal must be positive and within the fee vault's balance")]
+    InvalidFeeWithdrawal,
+}
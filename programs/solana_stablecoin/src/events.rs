@@ -1,10 +1,14 @@
 // events.rs
 use anchor_lang::prelude::*;
+use crate::states::{stablecoin::{KycPolicy, KycRequirements, MintPolicy, RedemptionMode}, protocol_params::ProtocolParam, flow_limits::{FlowDirection, FlowLimits}};
 
 #[event]
 pub struct FactoryInitialized {
+    pub factory: Pubkey,
+    pub factory_id: u64,
     pub admin: Pubkey,
     pub fee_vault: Pubkey,
+    pub fee_mint: Pubkey,
     pub min_collateral_ratio: u16,
     pub base_fee_rate: u16,
     pub protocol_version: u16,
@@ -14,6 +18,7 @@ pub struct FactoryInitialized {
 #[event]
 pub struct FactoryConfigUpdated {
     pub admin: Pubkey,
+    pub guardian: Pubkey,
     pub fee_vault: Pubkey,
     pub min_collateral_ratio: u16,
     pub base_fee_rate: u16,
@@ -23,12 +28,19 @@ pub struct FactoryConfigUpdated {
 
 #[event]
 pub struct StablecoinCreated {
+    pub factory: Pubkey,
     pub creator: Pubkey,
     pub mint: Pubkey,
     pub bond_mint: Pubkey,
     pub name: String,
     pub symbol: String,
     pub target_currency: String,
+    pub uri: String,
+    pub collateral_ratio: u16,
+    pub fee_rate: u16,
+    pub token_program: Pubkey,
+    pub index: u32,
+    pub compliance_mode: bool,
     pub timestamp: i64,
 }
 
@@ -48,6 +60,7 @@ pub struct StablecoinUpdated {
     pub mint: Pubkey,
     pub name: Option<String>,
     pub symbol: Option<String>,
+    pub uri: Option<String>,
     pub timestamp: i64,
 }
 
@@ -106,6 +119,7 @@ pub struct BondRemoved {
 pub struct StablecoinPaused {
     pub admin: Pubkey,
     pub stablecoin: Pubkey,
+    pub by_factory: bool,
     pub timestamp: i64,
 }
 
@@ -113,5 +127,293 @@ pub struct StablecoinPaused {
 pub struct StablecoinResumed {
     pub admin: Pubkey,
     pub stablecoin: Pubkey,
+    pub by_factory: bool,
+    pub is_paused: bool,             // Still paused by the other path
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct CollateralDeposited {
+    pub user: Pubkey,
+    pub stablecoin: Pubkey,
+    pub bond_amount: u64,
+    pub position_collateral: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralWithdrawn {
+    pub user: Pubkey,
+    pub stablecoin: Pubkey,
+    pub bond_amount: u64,
+    pub position_collateral: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StablecoinRedeemed {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub stablecoin_amount: u64,
+    pub bond_amount: u64,
+    pub fee_amount: u64,
+    pub bond_price: u64,
+    pub mode: RedemptionMode,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InterestRateUpdated {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub rate: i16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SymbolReserved {
+    pub admin: Pubkey,
+    pub symbol: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SymbolReleased {
+    pub admin: Pubkey,
+    pub symbol: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StablecoinAuthorityTransferStarted {
+    pub stablecoin: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StablecoinAuthorityTransferred {
+    pub stablecoin: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StablecoinOperatorsUpdated {
+    pub stablecoin: Pubkey,
+    pub authority: Pubkey,
+    pub metadata_editor: Option<Pubkey>,
+    pub pauser: Option<Pubkey>,
+    pub compliance_officer: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StablecoinShutdown {
+    pub admin: Pubkey,
+    pub stablecoin: Pubkey,
+    pub settlement_price: u64,
+    pub settlement_rate: u64,
+    pub total_supply: u64,
+    pub total_collateral: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StablecoinSettled {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub stablecoin_amount: u64,
+    pub bond_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u16,
+    pub to_version: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolParamUpdated {
+    pub admin: Pubkey,
+    pub stablecoin: Option<Pubkey>,     // Set for per-stablecoin overrides
+    pub param: ProtocolParam,
+    pub old_value: Option<i64>,
+    pub new_value: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycPolicyUpdated {
+    pub stablecoin: Pubkey,
+    pub admin: Pubkey,
+    pub old_policy: KycPolicy,
+    pub new_policy: KycPolicy,
+    pub old_requirements: KycRequirements,
+    pub new_requirements: KycRequirements,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycAttested {
+    pub factory: Pubkey,
+    pub user: Pubkey,
+    pub tier: u8,
+    pub jurisdiction: [u8; 2],
+    pub expires_at: i64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressDenylisted {
+    pub stablecoin: Pubkey,
+    pub address: Pubkey,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressRemovedFromDenylist {
+    pub stablecoin: Pubkey,
+    pub address: Pubkey,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderFrozen {
+    pub stablecoin: Pubkey,
+    pub holder: Pubkey,
+    pub token_account: Pubkey,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderThawed {
+    pub stablecoin: Pubkey,
+    pub holder: Pubkey,
+    pub token_account: Pubkey,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderSeized {
+    pub stablecoin: Pubkey,
+    pub holder: Pubkey,
+    pub token_account: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintPolicyUpdated {
+    pub stablecoin: Pubkey,
+    pub authority: Pubkey,
+    pub old_policy: MintPolicy,
+    pub new_policy: MintPolicy,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MinterAdded {
+    pub stablecoin: Pubkey,
+    pub minter: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MinterRemoved {
+    pub stablecoin: Pubkey,
+    pub minter: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FlowLimitsUpdated {
+    pub stablecoin: Pubkey,
+    pub admin: Pubkey,
+    pub old_limits: FlowLimits,
+    pub new_limits: FlowLimits,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FlowLimitApproached {
+    pub stablecoin: Pubkey,
+    pub user: Option<Pubkey>,       // None for the stablecoin-wide cap
+    pub direction: FlowDirection,
+    pub net_flow: u64,
+    pub limit: u64,
+    pub window_start: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FlowLimitReached {
+    pub stablecoin: Pubkey,
+    pub user: Option<Pubkey>,       // None for the stablecoin-wide cap
+    pub direction: FlowDirection,
+    pub net_flow: u64,
+    pub limit: u64,
+    pub window_start: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StabilityFeeAccrued {
+    pub stablecoin: Pubkey,
+    pub fee: u64,                   // Stablecoins minted to the fee vault
+    pub fee_index: u128,            // Cumulative rate index, scaled by RATE_INDEX_SCALE
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StabilityFeeUpdated {
+    pub stablecoin: Pubkey,
+    pub admin: Pubkey,
+    pub old_rate_bps: u16,
+    pub new_rate_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DebtRepaid {
+    pub stablecoin: Pubkey,
+    pub payer: Pubkey,
+    pub owner: Pubkey,              // Position whose debt was repaid
+    pub amount: u64,
+    pub remaining_debt: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StabilityFeesWithdrawn {
+    pub stablecoin: Pubkey,
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FxFeedSet {
+    pub factory: Pubkey,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub old_aggregator: Option<Pubkey>,
+    pub new_aggregator: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
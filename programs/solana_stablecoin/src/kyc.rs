@@ -0,0 +1,76 @@
+// kyc.rs
+use anchor_lang::prelude::*;
+use stablebond_sdk::{accounts::Kyc, find_kyc_pda, types::Discriminator};
+use crate::states::{kyc_attestation::KycAttestation, stablecoin::{KycPolicy, StablecoinState}};
+use crate::errors::StablecoinError;
+
+/// Checks `user`'s Etherfuse KYC record against a stablecoin's policy.
+///
+/// Etherfuse records an approval by creating the Kyc account at the user's PDA and
+/// revokes it by closing the account, so a live, program-owned account carrying
+/// the Kyc discriminator is an active approval.
+pub fn verify_kyc(kyc_info: &AccountInfo, user: &Pubkey, policy: KycPolicy) -> Result<()> {
+    if policy == KycPolicy::NotRequired {
+        return Ok(());
+    }
+
+    let (kyc_pda, _) = find_kyc_pda(*user);
+    require!(
+        kyc_info.key() == kyc_pda,
+        StablecoinError::InvalidKycAccount
+    );
+
+    // A closed record reverts to the system program with no data
+    require!(
+        kyc_info.owner == &stablebond_sdk::ID && !kyc_info.data_is_empty(),
+        StablecoinError::KycRevoked
+    );
+
+    let record = Kyc::from_bytes(&kyc_info.try_borrow_data()?)
+        .map_err(|_| StablecoinError::InvalidKycAccount)?;
+
+    require!(
+        record.discriminator == Discriminator::Kyc,
+        StablecoinError::InvalidKycAccount
+    );
+
+    Ok(())
+}
+
+/// Checks `user`'s KycAttestation against a stablecoin's tier and jurisdiction
+/// requirements. Only applies under KycPolicy::Required, on top of verify_kyc.
+pub fn verify_kyc_requirements(
+    attestation: Option<&KycAttestation>,
+    user: &Pubkey,
+    stablecoin: &StablecoinState,
+) -> Result<()> {
+    let requirements = &stablecoin.kyc_requirements;
+    if stablecoin.kyc_policy == KycPolicy::NotRequired || !requirements.needs_attestation() {
+        return Ok(());
+    }
+
+    let attestation = attestation.ok_or(StablecoinError::MissingKycAttestation)?;
+    require!(
+        attestation.user == *user && attestation.factory == stablecoin.factory,
+        StablecoinError::InvalidKycAccount
+    );
+    require!(
+        attestation.expires_at > Clock::get()?.unix_timestamp,
+        StablecoinError::KycAttestationExpired
+    );
+    require!(
+        attestation.tier >= requirements.min_tier,
+        StablecoinError::KycTierTooLow
+    );
+    require!(
+        requirements.allows_jurisdiction(&attestation.jurisdiction),
+        StablecoinError::JurisdictionNotAllowed
+    );
+
+    Ok(())
+}
+
+/// Whether `code` is an ISO 3166-1 alpha-2 country code in upper case
+pub fn is_valid_jurisdiction(code: &[u8; 2]) -> bool {
+    code.iter().all(u8::is_ascii_uppercase)
+}
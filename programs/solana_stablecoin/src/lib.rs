@@ -5,6 +5,10 @@ pub mod states;
 pub mod errors;
 pub mod constants;
 pub mod events;
+pub mod oracle;
+pub mod math;
+pub mod kyc;
+pub mod compliance;
 
 
 use contexts::*;
@@ -19,10 +23,11 @@ pub mod solana_stablecoin {
     // Factory Management Instructions
     pub fn initialize_factory(
         ctx: Context<InitializeFactory>,
+        factory_id: u64,
         min_collateral_ratio: u16,
         base_fee_rate: u16,
     ) -> Result<()> {
-        contexts::initialize_factory(ctx, min_collateral_ratio, base_fee_rate)
+        contexts::initialize_factory(ctx, factory_id, min_collateral_ratio, base_fee_rate)
     }
 
     pub fn update_factory_config(
@@ -31,27 +36,219 @@ pub mod solana_stablecoin {
         new_min_collateral_ratio: Option<u16>,
         new_base_fee_rate: Option<u16>,
         new_fee_recipient: Option<Pubkey>,
-        
+        new_guardian: Option<Pubkey>,
     ) -> Result<()> {
-        contexts::update_factory_config(ctx, new_admin, new_min_collateral_ratio, new_base_fee_rate, new_fee_recipient)
+        contexts::update_factory_config(ctx, new_admin, new_min_collateral_ratio, new_base_fee_rate, new_fee_recipient, new_guardian)
+    }
+
+    pub fn update_protocol_params(
+        ctx: Context<UpdateProtocolParams>,
+        updates: ProtocolParamsUpdate,
+    ) -> Result<()> {
+        contexts::update_protocol_params(ctx, updates)
+    }
+
+    pub fn set_fx_feed(
+        ctx: Context<SetFxFeed>,
+        base_currency: String,
+        quote_currency: String,
+    ) -> Result<()> {
+        contexts::set_fx_feed(ctx, base_currency, quote_currency)
     }
 
     // Stablecoin Creation and Management
+    #[allow(clippy::too_many_arguments)]
     pub fn create_stablecoin(
         ctx: Context<CreateStablecoin>,
         name: String,
         symbol: String,
         target_currency: String,
+        uri: String,
+        collateral_ratio: u16,
+        fee_rate: u16,
+        token_2022_config: Option<Token2022Config>,
+        compliance_mode: bool,
+        stability_fee_bps: u16,
     ) -> Result<()> {
-        contexts::create_stablecoin(ctx, name, symbol, target_currency)
+        contexts::create_stablecoin(
+            ctx,
+            name,
+            symbol,
+            target_currency,
+            uri,
+            collateral_ratio,
+            fee_rate,
+            token_2022_config,
+            compliance_mode,
+            stability_fee_bps,
+        )
     }
 
     pub fn update_stablecoin(
         ctx: Context<UpdateStablecoin>,
         name: Option<String>,
         symbol: Option<String>,
+        uri: Option<String>,
+    ) -> Result<()> {
+        contexts::update_stablecoin(ctx, name, symbol, uri)
+    }
+
+    pub fn transfer_stablecoin_authority(
+        ctx: Context<TransferStablecoinAuthority>,
+        new_authority: Option<Pubkey>,
     ) -> Result<()> {
-        contexts::update_stablecoin(ctx, name, symbol)
+        contexts::transfer_stablecoin_authority(ctx, new_authority)
+    }
+
+    pub fn accept_stablecoin_authority(
+        ctx: Context<AcceptStablecoinAuthority>,
+    ) -> Result<()> {
+        contexts::accept_stablecoin_authority(ctx)
+    }
+
+    pub fn set_stablecoin_operators(
+        ctx: Context<SetStablecoinOperators>,
+        metadata_editor: Option<Pubkey>,
+        pauser: Option<Pubkey>,
+        compliance_officer: Option<Pubkey>,
+    ) -> Result<()> {
+        contexts::set_stablecoin_operators(ctx, metadata_editor, pauser, compliance_officer)
+    }
+
+    pub fn reserve_symbol(
+        ctx: Context<ReserveSymbol>,
+        symbol: String,
+    ) -> Result<()> {
+        contexts::reserve_symbol(ctx, symbol)
+    }
+
+    pub fn release_symbol(
+        ctx: Context<ReleaseSymbol>,
+    ) -> Result<()> {
+        contexts::release_symbol(ctx)
+    }
+
+    pub fn set_stablecoin_param_overrides(
+        ctx: Context<SetStablecoinParamOverrides>,
+        overrides: StablecoinParamOverrides,
+    ) -> Result<()> {
+        contexts::set_stablecoin_param_overrides(ctx, overrides)
+    }
+
+    pub fn set_kyc_policy(
+        ctx: Context<SetKycPolicy>,
+        policy: KycPolicy,
+        requirements: KycRequirements,
+    ) -> Result<()> {
+        contexts::set_kyc_policy(ctx, policy, requirements)
+    }
+
+    pub fn attest_kyc(
+        ctx: Context<AttestKyc>,
+        user: Pubkey,
+        tier: u8,
+        jurisdiction: [u8; 2],
+        expires_at: i64,
+    ) -> Result<()> {
+        contexts::attest_kyc(ctx, user, tier, jurisdiction, expires_at)
+    }
+
+    // Compliance
+    pub fn add_to_denylist(
+        ctx: Context<AddToDenylist>,
+        address: Pubkey,
+    ) -> Result<()> {
+        contexts::add_to_denylist(ctx, address)
+    }
+
+    pub fn remove_from_denylist(
+        ctx: Context<RemoveFromDenylist>,
+    ) -> Result<()> {
+        contexts::remove_from_denylist(ctx)
+    }
+
+    pub fn freeze_holder(
+        ctx: Context<FreezeHolder>,
+    ) -> Result<()> {
+        contexts::freeze_holder(ctx)
+    }
+
+    pub fn thaw_holder(
+        ctx: Context<ThawHolder>,
+    ) -> Result<()> {
+        contexts::thaw_holder(ctx)
+    }
+
+    pub fn seize_holder<'info>(
+        ctx: Context<'_, '_, '_, 'info, SeizeHolder<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        contexts::seize_holder(ctx, amount)
+    }
+
+    // Minter Permissions
+    pub fn set_mint_policy(
+        ctx: Context<SetMintPolicy>,
+        policy: MintPolicy,
+    ) -> Result<()> {
+        contexts::set_mint_policy(ctx, policy)
+    }
+
+    pub fn add_minter(
+        ctx: Context<AddMinter>,
+        minter: Pubkey,
+    ) -> Result<()> {
+        contexts::add_minter(ctx, minter)
+    }
+
+    pub fn remove_minter(
+        ctx: Context<RemoveMinter>,
+    ) -> Result<()> {
+        contexts::remove_minter(ctx)
+    }
+
+    // Flow Limits
+    pub fn set_flow_limits(
+        ctx: Context<SetFlowLimits>,
+        limits: FlowLimits,
+    ) -> Result<()> {
+        contexts::set_flow_limits(ctx, limits)
+    }
+
+    // Stability Fee
+    pub fn set_stability_fee(
+        ctx: Context<SetStabilityFee>,
+        rate_bps: u16,
+    ) -> Result<()> {
+        contexts::set_stability_fee(ctx, rate_bps)
+    }
+
+    pub fn accrue_stability_fee(
+        ctx: Context<AccrueStabilityFee>,
+    ) -> Result<()> {
+        contexts::accrue_stability_fee(ctx)
+    }
+
+    pub fn withdraw_stability_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawStabilityFees<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        contexts::withdraw_stability_fees(ctx, amount)
+    }
+
+    pub fn repay_debt(
+        ctx: Context<RepayDebt>,
+        owner: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        contexts::repay_debt(ctx, owner, amount)
+    }
+
+    pub fn update_interest_rate(
+        ctx: Context<UpdateInterestRate>,
+        rate: i16,
+    ) -> Result<()> {
+        contexts::update_interest_rate(ctx, rate)
     }
 
     pub fn add_supported_bond(ctx: Context<AddSupportedBond>, min_creation_amount: u64, min_redemption_amount: u64) -> Result<()> {
@@ -72,16 +269,76 @@ pub mod solana_stablecoin {
     // Token Operations
     pub fn mint_tokens(
         ctx: Context<MintStablecoin>, 
-        bond_amount: u64
+        bond_amount: u64,
+        min_stablecoin_out: u64,
+        max_fee: u64,
+        expires_at: Option<i64>,
     ) -> Result<()> {
-        contexts::mint_stablecoin(ctx, bond_amount)
+        contexts::mint_stablecoin(ctx, bond_amount, min_stablecoin_out, max_fee, expires_at)
     }
 
     pub fn burn_tokens(
         ctx: Context<BurnStablecoin>,
         stablecoin_amount: u64,
+        min_bonds_out: u64,
+        max_fee: u64,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        contexts::burn_stablecoin(ctx, stablecoin_amount, min_bonds_out, max_fee, expires_at)
+    }
+
+    pub fn redeem(
+        ctx: Context<Redeem>,
+        stablecoin_amount: u64,
+        mode: RedemptionMode,
+        min_bonds_out: u64,
+    ) -> Result<()> {
+        contexts::redeem(ctx, stablecoin_amount, mode, min_bonds_out)
+    }
+
+    pub fn deposit_collateral(
+        ctx: Context<DepositCollateral>,
+        bond_amount: u64,
+    ) -> Result<()> {
+        contexts::deposit_collateral(ctx, bond_amount)
+    }
+
+    pub fn withdraw_collateral(
+        ctx: Context<WithdrawCollateral>,
+        bond_amount: u64,
     ) -> Result<()> {
-        contexts::burn_stablecoin(ctx, stablecoin_amount)
+        contexts::withdraw_collateral(ctx, bond_amount)
+    }
+
+    // Quotes
+    pub fn quote_mint(
+        ctx: Context<QuoteMint>,
+        user: Pubkey,
+        bond_amount: u64,
+    ) -> Result<MintQuote> {
+        contexts::quote_mint(ctx, user, bond_amount)
+    }
+
+    pub fn quote_burn(
+        ctx: Context<QuoteBurn>,
+        user: Pubkey,
+        stablecoin_amount: u64,
+    ) -> Result<BurnQuote> {
+        contexts::quote_burn(ctx, user, stablecoin_amount)
+    }
+
+    pub fn quote_yield(
+        ctx: Context<QuoteYield>,
+        user: Pubkey,
+    ) -> Result<YieldQuote> {
+        contexts::quote_yield(ctx, user)
+    }
+
+    pub fn quote_flow_capacity(
+        ctx: Context<QuoteFlowCapacity>,
+        user: Pubkey,
+    ) -> Result<FlowCapacity> {
+        contexts::quote_flow_capacity(ctx, user)
     }
 
     // Yield Management
@@ -103,4 +360,31 @@ pub mod solana_stablecoin {
     ) -> Result<()> {
         contexts::resume_stablecoin(ctx)
     }
+
+    // Account Migrations
+    pub fn migrate_factory(
+        ctx: Context<MigrateFactory>,
+    ) -> Result<()> {
+        contexts::migrate_factory(ctx)
+    }
+
+    pub fn migrate_stablecoin(
+        ctx: Context<MigrateStablecoin>,
+    ) -> Result<()> {
+        contexts::migrate_stablecoin(ctx)
+    }
+
+    // Global Settlement
+    pub fn shutdown_stablecoin(
+        ctx: Context<ShutdownStablecoin>,
+    ) -> Result<()> {
+        contexts::shutdown_stablecoin(ctx)
+    }
+
+    pub fn settle_stablecoin(
+        ctx: Context<SettleStablecoin>,
+        stablecoin_amount: u64,
+    ) -> Result<()> {
+        contexts::settle_stablecoin(ctx, stablecoin_amount)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,129 @@
+// math.rs
+//
+// Shared fixed-point arithmetic. Every division states its rounding direction so
+// that rounding always favors the protocol: amounts paid out round down, amounts
+// owed to the protocol (fees, required collateral) round up.
+use anchor_lang::prelude::*;
+use crate::errors::StablecoinError;
+
+/// Direction to round the result of a division
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// a * b / c rounded as requested, with a single rounding step
+pub fn mul_div(a: u128, b: u128, c: u128, rounding: Rounding) -> Result<u128> {
+    require!(c > 0, StablecoinError::DivideByZero);
+
+    let product = a
+        .checked_mul(b)
+        .ok_or(StablecoinError::MathOverflow)?;
+
+    let quotient = product / c;
+    match rounding {
+        Rounding::Up if product % c != 0 => quotient
+            .checked_add(1)
+            .ok_or(StablecoinError::MathOverflow.into()),
+        _ => Ok(quotient),
+    }
+}
+
+/// mul_div narrowed to u64
+pub fn mul_div_u64(a: u64, b: u64, c: u64, rounding: Rounding) -> Result<u64> {
+    to_u64(mul_div(a as u128, b as u128, c as u128, rounding)?)
+}
+
+/// Narrows a u128 result, failing instead of truncating
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| StablecoinError::MathOverflow.into())
+}
+
+/// Unsigned fixed-point number with 12 decimal places. Used where an
+/// intermediate value has a meaningful fractional part, such as a per-coin
+/// yield rate, so it is not truncated before being scaled back up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed(u128);
+
+impl Fixed {
+    pub const SCALE: u128 = 1_000_000_000_000;
+
+    /// numerator / denominator
+    pub fn from_ratio(numerator: u128, denominator: u128, rounding: Rounding) -> Result<Self> {
+        Ok(Fixed(mul_div(numerator, Self::SCALE, denominator, rounding)?))
+    }
+
+    /// self * value, exact
+    pub fn mul_int(self, value: u128) -> Result<Self> {
+        Ok(Fixed(self.0.checked_mul(value).ok_or(StablecoinError::MathOverflow)?))
+    }
+
+    /// self / value
+    pub fn div_int(self, value: u128, rounding: Rounding) -> Result<Self> {
+        Ok(Fixed(mul_div(self.0, 1, value, rounding)?))
+    }
+
+    /// Integer part, rounded as requested
+    pub fn to_u64(self, rounding: Rounding) -> Result<u64> {
+        to_u64(mul_div(self.0, 1, Self::SCALE, rounding)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn err(e: StablecoinError) -> Error {
+        e.into()
+    }
+
+    #[test]
+    fn mul_div_rounds_in_the_requested_direction() {
+        assert_eq!(mul_div(10, 1, 3, Rounding::Down).unwrap(), 3);
+        assert_eq!(mul_div(10, 1, 3, Rounding::Up).unwrap(), 4);
+        // Exact results are not bumped up
+        assert_eq!(mul_div(9, 1, 3, Rounding::Up).unwrap(), 3);
+        assert_eq!(mul_div(0, 7, 3, Rounding::Up).unwrap(), 0);
+    }
+
+    #[test]
+    fn mul_div_rejects_zero_denominator() {
+        assert_eq!(mul_div(1, 1, 0, Rounding::Down).unwrap_err(), err(StablecoinError::DivideByZero));
+        assert_eq!(mul_div_u64(1, 1, 0, Rounding::Up).unwrap_err(), err(StablecoinError::DivideByZero));
+    }
+
+    #[test]
+    fn mul_div_reports_overflow() {
+        assert_eq!(mul_div(u128::MAX, 2, 1, Rounding::Down).unwrap_err(), err(StablecoinError::MathOverflow));
+        assert_eq!(mul_div_u64(u64::MAX, 2, 1, Rounding::Down).unwrap_err(), err(StablecoinError::MathOverflow));
+        // Intermediate products wider than u64 are fine as long as the result fits
+        assert_eq!(mul_div_u64(u64::MAX, u64::MAX, u64::MAX, Rounding::Down).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn to_u64_fails_instead_of_truncating() {
+        assert_eq!(to_u64(u64::MAX as u128).unwrap(), u64::MAX);
+        assert_eq!(to_u64(u64::MAX as u128 + 1).unwrap_err(), err(StablecoinError::MathOverflow));
+    }
+
+    #[test]
+    fn fixed_keeps_fractional_part() {
+        let third = Fixed::from_ratio(1, 3, Rounding::Down).unwrap();
+        assert_eq!(third.mul_int(3).unwrap().to_u64(Rounding::Down).unwrap(), 0);
+        assert_eq!(third.mul_int(3).unwrap().to_u64(Rounding::Up).unwrap(), 1);
+
+        let half = Fixed::from_ratio(1, 2, Rounding::Down).unwrap();
+        let amount = half.mul_int(5).unwrap();
+        assert_eq!(amount.to_u64(Rounding::Down).unwrap(), 2);
+        assert_eq!(amount.to_u64(Rounding::Up).unwrap(), 3);
+
+        assert_eq!(Fixed::from_ratio(7, 1, Rounding::Down).unwrap().div_int(2, Rounding::Up).unwrap().to_u64(Rounding::Down).unwrap(), 3);
+    }
+
+    #[test]
+    fn fixed_checked_arithmetic() {
+        assert_eq!(Fixed::from_ratio(1, 0, Rounding::Down).unwrap_err(), err(StablecoinError::DivideByZero));
+        assert_eq!(Fixed::from_ratio(1, 1, Rounding::Down).unwrap().mul_int(u128::MAX).unwrap_err(), err(StablecoinError::MathOverflow));
+    }
+}
@@ -0,0 +1,215 @@
+// oracle.rs
+use anchor_lang::prelude::*;
+use stablebond_sdk::types::PaymentFeedType;
+use switchboard_solana::{AggregatorAccountData, AggregatorRound, SwitchboardDecimal};
+use crate::states::stablecoin::StablecoinState;
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::math::{mul_div, Rounding};
+
+/// Returns true if the ISO-4217 code is one the protocol can price
+pub fn is_supported_currency(currency: &str) -> bool {
+    SUPPORTED_TARGET_CURRENCIES.contains(&currency)
+}
+
+/// ISO-4217 currency a stablebond's payment feed is denominated in
+pub fn payment_feed_currency(feed_type: &PaymentFeedType) -> &'static str {
+    match feed_type {
+        PaymentFeedType::UsdcUsd |
+        PaymentFeedType::SwitchboardUsdcUsd |
+        PaymentFeedType::Stub => "USD",
+
+        PaymentFeedType::UsdcMxn |
+        PaymentFeedType::SwitchboardUsdcMxn => "MXN",
+
+        PaymentFeedType::SwitchboardUsdcBrl => "BRL",
+        PaymentFeedType::SwitchboardUsdcEur => "EUR",
+        PaymentFeedType::SwitchboardUsdcGbp => "GBP",
+    }
+}
+
+/// A feed result as a price scaled by PRICE_SCALE, normalizing the result's
+/// decimal scale. Prices can't be negative, so a mantissa at or below zero is
+/// rejected rather than taken as its absolute value.
+pub fn scaled_price(result: SwitchboardDecimal) -> Result<u64> {
+    require!(result.mantissa > 0, StablecoinError::InvalidOraclePrice);
+
+    let divisor = 10u128
+        .checked_pow(result.scale)
+        .ok_or(StablecoinError::MathOverflow)?;
+
+    let price = mul_div(result.mantissa as u128, PRICE_SCALE, divisor, Rounding::Down)?;
+
+    require!(price > 0 && price <= u64::MAX as u128, StablecoinError::InvalidOraclePrice);
+
+    Ok(price as u64)
+}
+
+/// The feed's latest confirmed round, failing if it opened more than
+/// `max_staleness` seconds ago
+pub fn load_fresh_round(
+    oracle: &AccountLoader<AggregatorAccountData>,
+    max_staleness: i64,
+) -> Result<AggregatorRound> {
+    let round = oracle.load()?.latest_confirmed_round;
+
+    require!(
+        Clock::get()?.unix_timestamp - round.round_open_timestamp <= max_staleness,
+        StablecoinError::StaleOraclePrice
+    );
+
+    Ok(round)
+}
+
+/// Loads an FX rate (target currency per unit of bond currency) scaled by PRICE_SCALE
+pub fn load_fx_rate(
+    fx_oracle: &AccountLoader<AggregatorAccountData>,
+    max_staleness: i64,
+) -> Result<u64> {
+    let round = load_fresh_round(fx_oracle, max_staleness)?;
+    scaled_price(round.result)
+}
+
+/// Converts a bond price in the bond's currency into the stablecoin's target currency.
+/// Stablecoins whose target matches the bond currency have no FX oracle and pass through.
+pub fn price_in_target_currency(
+    stablecoin: &StablecoinState,
+    bond_price: u64,
+    fx_oracle: &Option<AccountLoader<AggregatorAccountData>>,
+    max_staleness: i64,
+) -> Result<u64> {
+    if stablecoin.fiat_oracle == Pubkey::default() {
+        return Ok(bond_price);
+    }
+
+    let fx_oracle = fx_oracle
+        .as_ref()
+        .ok_or(StablecoinError::MissingOracleAccount)?;
+
+    require!(
+        fx_oracle.key() == stablecoin.fiat_oracle,
+        StablecoinError::InvalidOracleConfig
+    );
+
+    let fx_rate = load_fx_rate(fx_oracle, max_staleness)?;
+
+    let price = mul_div(bond_price as u128, fx_rate as u128, PRICE_SCALE, Rounding::Down)?;
+
+    require!(
+        price > 0 && price <= u64::MAX as u128,
+        StablecoinError::InvalidOraclePrice
+    );
+
+    Ok(price as u64)
+}
+
+/// Bond price from the bond's Switchboard feed, scaled by PRICE_SCALE and
+/// converted into the stablecoin's target currency
+pub fn load_bond_price(
+    oracle: &AccountLoader<AggregatorAccountData>,
+    stablecoin: &StablecoinState,
+    fx_oracle: &Option<AccountLoader<AggregatorAccountData>>,
+    max_staleness: i64,
+) -> Result<u64> {
+    let round = load_fresh_round(oracle, max_staleness)?;
+    let price = scaled_price(round.result)?;
+
+    price_in_target_currency(stablecoin, price, fx_oracle, max_staleness)
+}
+
+/// Rate applied to the bond feed for yield accrual, scaled by PRICE_SCALE.
+/// USD-paying bonds convert 1:1; the others use the feed's own rate.
+pub fn conversion_rate(
+    oracle: &AccountLoader<AggregatorAccountData>,
+    feed_type: &PaymentFeedType,
+    max_staleness: i64,
+) -> Result<u128> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let feed = oracle.load()?;
+
+    // Verify feed freshness
+    require!(
+        current_time - feed.latest_confirmed_round.round_open_timestamp <= max_staleness,
+        StablecoinError::StaleError
+    );
+
+    match feed_type {
+        // USD pairs 
+        PaymentFeedType::UsdcMxn | 
+        PaymentFeedType::SwitchboardUsdcMxn | 
+        PaymentFeedType::SwitchboardUsdcBrl | 
+        PaymentFeedType::SwitchboardUsdcEur | 
+        PaymentFeedType::SwitchboardUsdcGbp => {
+            msg!("Using oracle conversion rate for {:?}", feed_type);
+            Ok(scaled_price(feed.latest_confirmed_round.result)? as u128)
+        },
+
+        // USD/USD pairs
+        PaymentFeedType::UsdcUsd |
+        PaymentFeedType::SwitchboardUsdcUsd => {
+            msg!("Using 1:1 USD conversion");
+            Ok(PRICE_SCALE)
+        },
+
+        PaymentFeedType::Stub => {
+            msg!("Using stub conversion rate");
+            Ok(PRICE_SCALE)
+        }
+    }
+}
+
+/// Price used to accrue yield: the bond feed adjusted by its conversion rate
+pub fn load_yield_price(
+    oracle: &AccountLoader<AggregatorAccountData>,
+    feed_type: &PaymentFeedType,
+    max_staleness: i64,
+) -> Result<u128> {
+    let base_price = scaled_price(oracle.load()?.latest_confirmed_round.result)? as u128;
+    let conversion_rate = conversion_rate(oracle, feed_type, max_staleness)?;
+
+    mul_div(base_price, conversion_rate, PRICE_SCALE, Rounding::Down)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(mantissa: i128, scale: u32) -> SwitchboardDecimal {
+        SwitchboardDecimal { mantissa, scale }
+    }
+
+    #[test]
+    fn scaled_price_normalizes_the_result_scale() {
+        // 1.05 reported with 2, 6 and 9 decimals
+        assert_eq!(scaled_price(decimal(105, 2)).unwrap(), 1_050_000);
+        assert_eq!(scaled_price(decimal(1_050_000, 6)).unwrap(), 1_050_000);
+        assert_eq!(scaled_price(decimal(1_050_000_000, 9)).unwrap(), 1_050_000);
+        assert_eq!(scaled_price(decimal(20, 0)).unwrap(), 20_000_000);
+    }
+
+    #[test]
+    fn scaled_price_rounds_down() {
+        // 1.0000009 truncates below PRICE_SCALE precision
+        assert_eq!(scaled_price(decimal(10_000_009, 7)).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn scaled_price_rejects_non_positive_mantissas() {
+        let invalid: Error = StablecoinError::InvalidOraclePrice.into();
+        assert_eq!(scaled_price(decimal(-1_050_000, 6)).unwrap_err(), invalid);
+        assert_eq!(scaled_price(decimal(0, 6)).unwrap_err(), invalid);
+        // Too small to register at PRICE_SCALE precision
+        assert_eq!(scaled_price(decimal(1, 9)).unwrap_err(), invalid);
+    }
+
+    #[test]
+    fn scaled_price_rejects_out_of_range_results() {
+        let overflow: Error = StablecoinError::MathOverflow.into();
+        assert_eq!(scaled_price(decimal(1, 40)).unwrap_err(), overflow);
+        assert_eq!(scaled_price(decimal(i128::MAX, 0)).unwrap_err(), overflow);
+        assert_eq!(
+            scaled_price(decimal(u64::MAX as i128, 0)).unwrap_err(),
+            StablecoinError::InvalidOraclePrice.into()
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// DenylistEntry bars an address from minting a compliance-mode stablecoin
+/// and from every exit: burning, redemption, collateral withdrawal and
+/// settlement. The entry exists only while the address is denied.
+#[account]
+#[derive(InitSpace)]
+pub struct DenylistEntry {
+    pub stablecoin: Pubkey,
+    pub address: Pubkey,
+    pub added_by: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
@@ -4,22 +4,36 @@ use stablebond_sdk::{
     accounts::{Bond, PaymentFeed},
     find_bond_pda, find_payment_feed_pda,
 };
-use crate::states::{bond_config::StablebondConfig, bond_tracker::BondCollateralInfo};
+use crate::states::{bond_config::StablebondConfig, bond_tracker::BondCollateralInfo, protocol_params::ProtocolParams};
 use crate::errors::StablecoinError;
 use crate::constants::*;
 
+/// Extra PDA seed identifying a factory. Factory 0 is the original singleton
+/// seeded by the bare prefix, so its id contributes no seed bytes.
+pub fn factory_id_seed(factory_id: u64) -> Vec<u8> {
+    if factory_id == 0 {
+        Vec::new()
+    } else {
+        factory_id.to_le_bytes().to_vec()
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct FactoryState {
     // Authority and control
+    pub factory_id: u64,                  // Distinguishes independent factories within one deployment
     pub admin: Pubkey,                    // Account authorized to update initialize factory and update factory configs
+    pub guardian: Pubkey,                 // Account authorized to pause any stablecoin in the factory
     pub fee_vault: Pubkey,            // Account that holds the fees. Mint fees, Yield fees, Burn fees.
+    pub fee_mint: Pubkey,                 // Mint for fees and yield, USDC on mainnet
     pub is_paused: bool,                  // If the factory has been paused or not
     
     // Protocol parameters
     pub min_collateral_ratio: u16,        // The minimum collateral ratio needed to mint a stablecoin (e.g. 15000 = 150%)
     pub base_fee_rate: u16,               // The default fee percentage that goes to the fee_recipient account
     pub stablecoin_count: u32,           // tracks the total number of different stablecoins created 
+    pub protocol_params: ProtocolParams, // Admin-configurable tunables, see update_protocol_params
 
     pub last_update: i64,                // Unix timestamp of the last protocol update
 
@@ -34,13 +48,23 @@ pub struct FactoryState {
     pub authorized_collectors: Vec<Pubkey>,
     
     // Admin controls
-    pub protocol_version: u16,           // For tracking protocol upgrades
+    pub protocol_version: u16,           // Account schema version, see FACTORY_SCHEMA_VERSION
     pub bump: u8,                        // PDA bump
     pub reserved: [u8; 32],              // 32 bytes of free space to prevent account size issues and for future upgrades
 }
 
 impl FactoryState {
 
+    /// Seed bytes for this factory's PDAs, see factory_id_seed
+    pub fn id_seed(&self) -> Vec<u8> {
+        factory_id_seed(self.factory_id)
+    }
+
+    /// Whether the account has been migrated to the current layout
+    pub fn is_current(&self) -> bool {
+        self.protocol_version == FACTORY_SCHEMA_VERSION
+    }
+
     pub fn is_authorized_collector(&self, collector: Pubkey) -> bool {
         self.authorized_collectors.contains(&collector)
     }
@@ -52,7 +76,7 @@ impl FactoryState {
         );
 
         require!(
-            self.authorized_collectors.len() < MAX_ALLOWED_COLLECTORS,
+            self.authorized_collectors.len() < self.protocol_params.max_allowed_collectors as usize,
             StablecoinError::MaxCollectorsReached
         );
 
@@ -105,7 +129,7 @@ impl FactoryState {
     ) -> Result<()> {
         // Ensure we don't exceed max bonds
         require!(
-            self.allowed_bond_configs.len() < 10,  // max_len(10)
+            self.allowed_bond_configs.len() < self.protocol_params.max_allowed_bonds as usize,
             StablecoinError::TooManyBonds
         );
 
@@ -0,0 +1,313 @@
+// states/flow_limits.rs
+use anchor_lang::prelude::*;
+use crate::states::stablecoin::StablecoinState;
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::events::*;
+use crate::math::{mul_div_u64, Rounding};
+
+/// Caps on how many stablecoins can be minted or redeemed within a window.
+/// Each cap applies to flow net of the opposite direction; zero means no cap.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct FlowLimits {
+    pub window_duration: i64,       // Window length in seconds, 0 disables every cap
+    pub max_net_mint: u64,          // Across all holders, per window
+    pub max_net_redeem: u64,
+    pub user_max_net_mint: u64,     // For each holder, per window
+    pub user_max_net_redeem: u64,
+}
+
+impl FlowLimits {
+    pub fn is_enabled(&self) -> bool {
+        self.window_duration > 0
+    }
+
+    /// Stablecoin-wide cap for `direction`
+    pub fn max_net(&self, direction: FlowDirection) -> u64 {
+        match direction {
+            FlowDirection::Mint => self.max_net_mint,
+            FlowDirection::Redeem => self.max_net_redeem,
+        }
+    }
+
+    /// Per-holder cap for `direction`
+    pub fn user_max_net(&self, direction: FlowDirection) -> u64 {
+        match direction {
+            FlowDirection::Mint => self.user_max_net_mint,
+            FlowDirection::Redeem => self.user_max_net_redeem,
+        }
+    }
+}
+
+/// Which way stablecoins flow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlowDirection {
+    Mint,       // Stablecoins issued against collateral
+    Redeem,     // Stablecoins burned for collateral, or collateral withdrawn at face value
+}
+
+/// Stablecoins minted and redeemed in the current window. A window opens with
+/// the first flow after the previous one has elapsed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct FlowWindow {
+    pub window_start: i64,
+    pub minted: u64,
+    pub redeemed: u64,
+}
+
+impl FlowWindow {
+    fn is_elapsed(&self, window_duration: i64, now: i64) -> bool {
+        now >= self.window_start.saturating_add(window_duration)
+    }
+
+    /// When the current window closes
+    pub fn window_end(&self, window_duration: i64) -> i64 {
+        self.window_start.saturating_add(window_duration)
+    }
+
+    /// Net flow in `direction` within the window open at `now`
+    pub fn net_flow(&self, direction: FlowDirection, window_duration: i64, now: i64) -> u64 {
+        if self.is_elapsed(window_duration, now) {
+            return 0;
+        }
+
+        match direction {
+            FlowDirection::Mint => self.minted.saturating_sub(self.redeemed),
+            FlowDirection::Redeem => self.redeemed.saturating_sub(self.minted),
+        }
+    }
+
+    /// Capacity left under `limit` at `now`. No cap reports u64::MAX.
+    pub fn remaining(&self, direction: FlowDirection, limit: u64, window_duration: i64, now: i64) -> u64 {
+        if window_duration == 0 || limit == 0 {
+            return u64::MAX;
+        }
+
+        limit.saturating_sub(self.net_flow(direction, window_duration, now))
+    }
+
+    /// Records `amount` of flow, opening a new window if the current one has
+    /// elapsed, and fails with `error` if the net flow would exceed `limit`.
+    /// Returns the net flow in `direction` before and after.
+    pub fn record(
+        &mut self,
+        direction: FlowDirection,
+        amount: u64,
+        limit: u64,
+        window_duration: i64,
+        now: i64,
+        error: StablecoinError,
+    ) -> Result<(u64, u64)> {
+        if self.is_elapsed(window_duration, now) {
+            *self = FlowWindow {
+                window_start: now,
+                minted: 0,
+                redeemed: 0,
+            };
+        }
+
+        let before = self.net_flow(direction, window_duration, now);
+
+        let counter = match direction {
+            FlowDirection::Mint => &mut self.minted,
+            FlowDirection::Redeem => &mut self.redeemed,
+        };
+        *counter = counter
+            .checked_add(amount)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        let after = self.net_flow(direction, window_duration, now);
+        if limit > 0 && after > limit {
+            return Err(error.into());
+        }
+
+        Ok((before, after))
+    }
+}
+
+/// Records a mint or redemption against the stablecoin's window and the
+/// holder's, failing if either cap would be exceeded. Emits FlowLimitApproached
+/// when a cap crosses FLOW_LIMIT_WARNING_BPS and FlowLimitReached when it is
+/// used up.
+pub fn record_flow(
+    stablecoin: &mut Account<'_, StablecoinState>,
+    user: Pubkey,
+    user_window: &mut FlowWindow,
+    direction: FlowDirection,
+    amount: u64,
+) -> Result<()> {
+    let limits = stablecoin.flow_limits.clone();
+    if !limits.is_enabled() {
+        return Ok(());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_key = stablecoin.key();
+
+    let limit = limits.max_net(direction);
+    let (before, after) = stablecoin.flow_window.record(
+        direction,
+        amount,
+        limit,
+        limits.window_duration,
+        now,
+        StablecoinError::FlowLimitExceeded,
+    )?;
+    report_flow(stablecoin_key, None, direction, before, after, limit, stablecoin.flow_window.window_start, now)?;
+
+    let user_limit = limits.user_max_net(direction);
+    let (before, after) = user_window.record(
+        direction,
+        amount,
+        user_limit,
+        limits.window_duration,
+        now,
+        StablecoinError::UserFlowLimitExceeded,
+    )?;
+    report_flow(stablecoin_key, Some(user), direction, before, after, user_limit, user_window.window_start, now)?;
+
+    Ok(())
+}
+
+/// Checks that `amount` more flow fits under the stablecoin's and the
+/// holder's caps at `now`, without recording it. Used by the quotes.
+pub fn check_flow(
+    stablecoin: &StablecoinState,
+    user_window: &FlowWindow,
+    direction: FlowDirection,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    let limits = &stablecoin.flow_limits;
+    if !limits.is_enabled() {
+        return Ok(());
+    }
+
+    let mut window = stablecoin.flow_window;
+    window.record(
+        direction,
+        amount,
+        limits.max_net(direction),
+        limits.window_duration,
+        now,
+        StablecoinError::FlowLimitExceeded,
+    )?;
+
+    let mut user_window = *user_window;
+    user_window.record(
+        direction,
+        amount,
+        limits.user_max_net(direction),
+        limits.window_duration,
+        now,
+        StablecoinError::UserFlowLimitExceeded,
+    )?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn report_flow(
+    stablecoin: Pubkey,
+    user: Option<Pubkey>,
+    direction: FlowDirection,
+    before: u64,
+    after: u64,
+    limit: u64,
+    window_start: i64,
+    timestamp: i64,
+) -> Result<()> {
+    if limit == 0 || after <= before {
+        return Ok(());
+    }
+
+    let warning = mul_div_u64(limit, FLOW_LIMIT_WARNING_BPS as u64, BPS_SCALE as u64, Rounding::Up)?;
+
+    if after >= limit {
+        emit!(FlowLimitReached {
+            stablecoin,
+            user,
+            direction,
+            net_flow: after,
+            limit,
+            window_start,
+            timestamp,
+        });
+    } else if after >= warning && before < warning {
+        emit!(FlowLimitApproached {
+            stablecoin,
+            user,
+            direction,
+            net_flow: after,
+            limit,
+            window_start,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3_600;
+
+    #[test]
+    fn record_nets_opposite_flows() {
+        let mut window = FlowWindow::default();
+        window.record(FlowDirection::Mint, 100, 150, HOUR, 10, StablecoinError::FlowLimitExceeded).unwrap();
+        window.record(FlowDirection::Redeem, 60, 0, HOUR, 20, StablecoinError::FlowLimitExceeded).unwrap();
+
+        // 100 minted net of 60 redeemed leaves room for 110 more under a cap of 150
+        assert_eq!(window.net_flow(FlowDirection::Mint, HOUR, 30), 40);
+        assert_eq!(window.net_flow(FlowDirection::Redeem, HOUR, 30), 0);
+        assert_eq!(window.remaining(FlowDirection::Mint, 150, HOUR, 30), 110);
+
+        let (before, after) = window
+            .record(FlowDirection::Mint, 110, 150, HOUR, 40, StablecoinError::FlowLimitExceeded)
+            .unwrap();
+        assert_eq!((before, after), (40, 150));
+    }
+
+    #[test]
+    fn record_rejects_flow_over_the_cap() {
+        let mut window = FlowWindow::default();
+        window.record(FlowDirection::Redeem, 90, 100, HOUR, 0, StablecoinError::UserFlowLimitExceeded).unwrap();
+
+        let result = window.record(FlowDirection::Redeem, 11, 100, HOUR, 1, StablecoinError::UserFlowLimitExceeded);
+        assert_eq!(result.unwrap_err(), StablecoinError::UserFlowLimitExceeded.into());
+    }
+
+    #[test]
+    fn window_resets_once_elapsed() {
+        let mut window = FlowWindow::default();
+        window.record(FlowDirection::Mint, 100, 100, HOUR, 10_000, StablecoinError::FlowLimitExceeded).unwrap();
+        assert_eq!(window.window_end(HOUR), 10_000 + HOUR);
+
+        // Still inside the window
+        assert_eq!(window.remaining(FlowDirection::Mint, 100, HOUR, 10_000 + HOUR - 1), 0);
+
+        // The window closes exactly at window_start + duration
+        assert_eq!(window.remaining(FlowDirection::Mint, 100, HOUR, 10_000 + HOUR), 100);
+        window.record(FlowDirection::Mint, 100, 100, HOUR, 10_000 + HOUR, StablecoinError::FlowLimitExceeded).unwrap();
+        assert_eq!(window.window_start, 10_000 + HOUR);
+        assert_eq!(window.minted, 100);
+    }
+
+    #[test]
+    fn zero_limit_or_duration_is_uncapped() {
+        let window = FlowWindow::default();
+        assert_eq!(window.remaining(FlowDirection::Mint, 0, HOUR, 0), u64::MAX);
+        assert_eq!(window.remaining(FlowDirection::Mint, 100, 0, 0), u64::MAX);
+        assert!(!FlowLimits::default().is_enabled());
+    }
+
+    #[test]
+    fn record_reports_overflow() {
+        let mut window = FlowWindow { window_start: 0, minted: u64::MAX, redeemed: 0 };
+        let result = window.record(FlowDirection::Mint, 1, 0, HOUR, 1, StablecoinError::FlowLimitExceeded);
+        assert_eq!(result.unwrap_err(), StablecoinError::MathOverflow.into());
+    }
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// FxFeed records the Switchboard aggregator the factory admin trusts to
+/// convert `base_currency` into `quote_currency`. A stablecoin whose target
+/// currency differs from its bond's payment currency is priced through the
+/// feed registered for that pair.
+#[account]
+#[derive(InitSpace)]
+pub struct FxFeed {
+    pub factory: Pubkey,
+    #[max_len(10)]
+    pub base_currency: String,      // ISO-4217 code of the bond's payment currency
+    #[max_len(10)]
+    pub quote_currency: String,     // ISO-4217 code of the stablecoin's target currency
+    pub aggregator: Pubkey,
+    pub updated_by: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+}
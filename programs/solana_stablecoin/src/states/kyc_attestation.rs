@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// KycAttestation records a holder's KYC tier and jurisdiction, attested by the
+/// factory admin from the holder's Etherfuse verification. Etherfuse's own
+/// record only says whether a holder is approved, so stablecoins that require a
+/// minimum tier or restrict jurisdictions check this account instead.
+#[account]
+#[derive(InitSpace)]
+pub struct KycAttestation {
+    pub factory: Pubkey,
+    pub user: Pubkey,
+    pub tier: u8,                   // Verification level, higher is stronger
+    pub jurisdiction: [u8; 2],      // ISO 3166-1 alpha-2 country code
+    pub expires_at: i64,            // Attestation lapses at this time
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,201 @@
+// states/legacy.rs
+//
+// Frozen copies of account layouts that shipped before schema versioning.
+// These must never change: migrations decode existing accounts with them.
+use anchor_lang::prelude::*;
+use crate::states::{
+    bond_config::StablebondConfig,
+    bond_tracker::BondCollateralInfo,
+    factory_state::FactoryState,
+    stablecoin::{KycPolicy, KycRequirements, MintPolicy, StablecoinState},
+    flow_limits::{FlowLimits, FlowWindow},
+    user::UserShare,
+    protocol_params::{ProtocolParams, StablecoinParamOverrides},
+};
+use crate::constants::*;
+
+/// FactoryState as first deployed (schema version 1, unversioned on-chain)
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FactoryStateV1 {
+    pub admin: Pubkey,
+    pub fee_vault: Pubkey,
+    pub is_paused: bool,
+    pub min_collateral_ratio: u16,
+    pub base_fee_rate: u16,
+    pub stablecoin_count: u32,
+    pub last_update: i64,
+    #[max_len(10)]
+    pub allowed_bond_configs: Vec<StablebondConfig>,
+    #[max_len(10)]
+    pub bond_collateral_tracking: Vec<BondCollateralInfo>,
+    #[max_len(5)]
+    pub authorized_collectors: Vec<Pubkey>,
+    pub protocol_version: u16,
+    pub bump: u8,
+    pub reserved: [u8; 32],
+}
+
+impl FactoryStateV1 {
+    pub fn into_current(self) -> FactoryState {
+        FactoryState {
+            factory_id: 0,
+            admin: self.admin,
+            guardian: self.admin,
+            fee_vault: self.fee_vault,
+            fee_mint: USDC_MINT,
+            is_paused: self.is_paused,
+            min_collateral_ratio: self.min_collateral_ratio,
+            base_fee_rate: self.base_fee_rate,
+            stablecoin_count: self.stablecoin_count,
+            protocol_params: ProtocolParams::default(),
+            last_update: self.last_update,
+            allowed_bond_configs: self.allowed_bond_configs,
+            bond_collateral_tracking: self.bond_collateral_tracking,
+            authorized_collectors: self.authorized_collectors,
+            protocol_version: FACTORY_SCHEMA_VERSION,
+            bump: self.bump,
+            reserved: self.reserved,
+        }
+    }
+}
+
+/// StablecoinState as first deployed (schema version 1, unversioned on-chain)
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct StablecoinStateV1 {
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    #[max_len(10)]
+    pub original_symbol: String,
+    #[max_len(10)]
+    pub target_currency: String,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub last_updated: i64,
+    #[max_len(100)]
+    pub user_shares: Vec<UserShare>,
+    pub stablebond_mint: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub collateral_ratio: u16,
+    pub total_supply: u64,
+    pub total_collateral: u64,
+    pub last_rebase: i64,
+    pub total_rebase_amount: u64,
+    pub last_yield_collection: i64,
+    pub last_rate_update: i64,
+    pub yield_mint: Pubkey,
+    pub total_yield_collected: u64,
+    pub bond_mint: Pubkey,
+    pub fiat_oracle: Pubkey,
+    pub last_price_update: i64,
+    pub is_paused: bool,
+    pub fee_rate: u16,
+    pub last_fee_collection: i64,
+    pub bump: u8,
+    pub reserved: [u8; 32],
+}
+
+impl StablecoinStateV1 {
+    /// Version 1 coins were always classic SPL Token mints managed by their creator
+    /// under the singleton factory, minted with STABLECOIN_DECIMALS. Version 1
+    /// stored no scales, so the bond and yield scales come from their mints.
+    pub fn into_current(self, factory: Pubkey, bond_scale: u64, yield_scale: u64) -> StablecoinState {
+        StablecoinState {
+            name: self.name,
+            symbol: self.symbol,
+            original_symbol: self.original_symbol,
+            target_currency: self.target_currency,
+            uri: String::new(),
+            factory,
+            creator: self.creator,
+            mint: self.mint,
+            token_program: anchor_spl::token::ID,
+            last_updated: self.last_updated,
+            user_shares: self.user_shares,
+            stablebond_mint: self.stablebond_mint,
+            collateral_vault: self.collateral_vault,
+            collateral_ratio: self.collateral_ratio,
+            total_supply: self.total_supply,
+            total_collateral: self.total_collateral,
+            last_rebase: self.last_rebase,
+            total_rebase_amount: self.total_rebase_amount,
+            last_yield_collection: self.last_yield_collection,
+            last_rate_update: self.last_rate_update,
+            yield_mint: self.yield_mint,
+            total_yield_collected: self.total_yield_collected,
+            bond_mint: self.bond_mint,
+            bond_scale,
+            stablecoin_scale: STABLECOIN_SCALE,
+            yield_scale,
+            fiat_oracle: self.fiat_oracle,
+            paused_by_authority: self.is_paused,
+            paused_by_factory: false,
+            fee_rate: self.fee_rate,
+            last_fee_collection: self.last_fee_collection,
+            stability_fee_bps: 0,
+            stability_fee_index: RATE_INDEX_SCALE,
+            last_stability_fee_accrual: 0,
+            total_stability_fees: 0,
+            param_overrides: StablecoinParamOverrides::default(),
+            kyc_policy: KycPolicy::Required,
+            compliance_mode: false,
+            mint_policy: MintPolicy::Open,
+            flow_limits: FlowLimits::default(),
+            flow_window: FlowWindow::default(),
+            is_shutdown: false,
+            settlement_price: 0,
+            settlement_rate: 0,
+            shutdown_at: 0,
+            authority: self.creator,
+            pending_authority: None,
+            metadata_editor: None,
+            pauser: None,
+            compliance_officer: None,
+            schema_version: STABLECOIN_SCHEMA_VERSION,
+            bump: self.bump,
+            kyc_requirements: KycRequirements::default(),
+            reserved: [0; 15],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_sizes_never_collide_with_current_layouts() {
+        // Migrations tell the versions apart by allocation size alone
+        assert_ne!(StablecoinStateV1::INIT_SPACE, StablecoinState::INIT_SPACE);
+        assert_ne!(FactoryStateV1::INIT_SPACE, FactoryState::INIT_SPACE);
+    }
+
+    #[test]
+    fn v1_stablecoin_upgrades_with_conservative_defaults() {
+        let mut legacy = StablecoinStateV1::deserialize(
+            &mut &vec![0u8; StablecoinStateV1::INIT_SPACE][..]
+        ).unwrap();
+        legacy.symbol = "USDX".to_string();
+        legacy.creator = Pubkey::new_unique();
+        legacy.total_supply = 1_000;
+        legacy.is_paused = true;
+
+        let bytes = legacy.try_to_vec().unwrap();
+        assert!(bytes.len() <= StablecoinStateV1::INIT_SPACE);
+
+        let factory = Pubkey::new_unique();
+        let creator = legacy.creator;
+        let current = StablecoinStateV1::deserialize(&mut &bytes[..]).unwrap().into_current(factory, 1_000_000_000, 1_000_000);
+        assert_eq!(current.symbol, "USDX");
+        assert_eq!((current.bond_scale, current.yield_scale), (1_000_000_000, 1_000_000));
+        assert_eq!(current.factory, factory);
+        assert_eq!(current.authority, creator);
+        assert_eq!(current.total_supply, 1_000);
+        assert!(current.paused_by_authority);
+        assert!(current.is_current());
+        assert_eq!(current.kyc_policy, KycPolicy::Required);
+        assert_eq!(current.stability_fee_index, RATE_INDEX_SCALE);
+        assert_eq!(current.token_program, anchor_spl::token::ID);
+    }
+}
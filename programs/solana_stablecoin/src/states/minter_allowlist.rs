@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// MinterAllowlistEntry approves a minter for a stablecoin whose mint policy is
+/// AllowlistOnly. The entry exists only while the minter is approved.
+#[account]
+#[derive(InitSpace)]
+pub struct MinterAllowlistEntry {
+    pub stablecoin: Pubkey,
+    pub minter: Pubkey,
+    pub added_by: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
@@ -10,6 +10,9 @@ pub use fee_config::*;
 pub mod bond_config;
 pub use bond_config::*;
 
+pub mod token_config;
+pub use token_config::*;
+
 pub mod bond_tracker;
 pub use bond_tracker::*;
 
@@ -17,4 +20,35 @@ pub mod user;
 pub use user::*;
 
 pub mod sol_fee_vault;
-pub use sol_fee_vault::*;
\ No newline at end of file
+pub use sol_fee_vault::*;
+
+pub mod symbol_registry;
+pub use symbol_registry::*;
+
+
+pub mod stablecoin_index;
+pub use stablecoin_index::*;
+
+pub mod legacy;
+pub use legacy::*;
+
+pub mod protocol_params;
+pub use protocol_params::*;
+
+pub mod quote;
+pub use quote::*;
+
+pub mod denylist;
+pub use denylist::*;
+
+pub mod minter_allowlist;
+pub use minter_allowlist::*;
+
+pub mod flow_limits;
+pub use flow_limits::*;
+
+pub mod fx_feed;
+pub use fx_feed::*;
+
+pub mod kyc_attestation;
+pub use kyc_attestation::*;
@@ -0,0 +1,101 @@
+// protocol_params.rs
+use anchor_lang::prelude::*;
+use crate::errors::StablecoinError;
+use crate::constants::*;
+
+/// Protocol-wide tunables stored on FactoryState so they can change without
+/// a program upgrade. The compile-time constants are the initial values.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct ProtocolParams {
+    pub oracle_staleness_threshold: i64,    // Max age of a bond or FX price, in seconds
+    pub rate_freshness_threshold: i64,      // Max age of a payment feed rate for yield, in seconds
+    pub rate_update_interval: i64,          // Max time since the last rate update for yield, in seconds
+    pub rebase_interval: i64,               // Min time between yield distributions, in seconds
+    pub protocol_fee_bps: u16,              // Protocol share of distributed yield
+    pub redemption_fee_bps: u16,            // Fee on peg redemptions, paid in bonds
+    pub max_allowed_bonds: u8,              // Bounded by the MAX_ALLOWED_BONDS account capacity
+    pub max_allowed_collectors: u8,         // Bounded by the MAX_ALLOWED_COLLECTORS account capacity
+}
+
+impl Default for ProtocolParams {
+    fn default() -> Self {
+        Self {
+            oracle_staleness_threshold: ORACLE_STALENESS_THRESHOLD,
+            rate_freshness_threshold: RATE_FRESHNESS_THRESHOLD,
+            rate_update_interval: RATE_UPDATE_INTERVAL,
+            rebase_interval: REBASE_INTERVAL,
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+            redemption_fee_bps: REDEMPTION_FEE_BPS,
+            max_allowed_bonds: MAX_ALLOWED_BONDS as u8,
+            max_allowed_collectors: MAX_ALLOWED_COLLECTORS as u8,
+        }
+    }
+}
+
+/// Identifies a parameter in ProtocolParamUpdated events
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtocolParam {
+    OracleStalenessThreshold,
+    RateFreshnessThreshold,
+    RateUpdateInterval,
+    RebaseInterval,
+    ProtocolFeeBps,
+    RedemptionFeeBps,
+    MaxAllowedBonds,
+    MaxAllowedCollectors,
+}
+
+impl ProtocolParam {
+    /// Rejects values outside the protocol's sane bounds
+    pub fn validate(&self, value: i64) -> Result<()> {
+        let (min, max, error) = match self {
+            ProtocolParam::OracleStalenessThreshold => (
+                MIN_ORACLE_STALENESS_THRESHOLD, MAX_ORACLE_STALENESS_THRESHOLD, StablecoinError::InvalidOracleStaleness
+            ),
+            ProtocolParam::RateFreshnessThreshold => (
+                MIN_ORACLE_STALENESS_THRESHOLD, MAX_RATE_FRESHNESS_THRESHOLD, StablecoinError::InvalidProtocolParam
+            ),
+            ProtocolParam::RateUpdateInterval |
+            ProtocolParam::RebaseInterval => (
+                MIN_REBASE_INTERVAL, MAX_REBASE_INTERVAL, StablecoinError::InvalidProtocolParam
+            ),
+            ProtocolParam::ProtocolFeeBps => (
+                0, MAX_PROTOCOL_FEE_BPS as i64, StablecoinError::InvalidFeeRate
+            ),
+            ProtocolParam::RedemptionFeeBps => (
+                0, MAX_REDEMPTION_FEE_BPS as i64, StablecoinError::InvalidFeeRate
+            ),
+            ProtocolParam::MaxAllowedBonds => (
+                1, MAX_ALLOWED_BONDS as i64, StablecoinError::InvalidProtocolParam
+            ),
+            ProtocolParam::MaxAllowedCollectors => (
+                1, MAX_ALLOWED_COLLECTORS as i64, StablecoinError::InvalidProtocolParam
+            ),
+        };
+
+        if !(min..=max).contains(&value) {
+            return Err(error.into());
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProtocolParamsUpdate {
+    pub oracle_staleness_threshold: Option<i64>,
+    pub rate_freshness_threshold: Option<i64>,
+    pub rate_update_interval: Option<i64>,
+    pub rebase_interval: Option<i64>,
+    pub protocol_fee_bps: Option<u16>,
+    pub redemption_fee_bps: Option<u16>,
+    pub max_allowed_bonds: Option<u8>,
+    pub max_allowed_collectors: Option<u8>,
+}
+
+/// Per-stablecoin overrides for parameters that depend on the coin's risk profile,
+/// e.g. a thinly traded FX pair needing a wider staleness window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, InitSpace)]
+pub struct StablecoinParamOverrides {
+    pub oracle_staleness_threshold: Option<i64>,
+    pub redemption_fee_bps: Option<u16>,
+}
@@ -0,0 +1,48 @@
+// states/quote.rs
+use anchor_lang::prelude::*;
+
+/// Result of quote_mint, returned via return data. Ratios are in basis points;
+/// a position or pool without debt reports u64::MAX.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MintQuote {
+    pub bond_amount: u64,           // Bonds deposited
+    pub mint_amount: u64,           // Stablecoins minted
+    pub fee: u64,                   // Fee charged to the minter, in lamports
+    pub bond_price: u64,            // Price used, scaled by PRICE_SCALE
+    pub position_ratio: u64,        // Minter's collateral ratio after the mint
+    pub pool_ratio: u64,            // Stablecoin's collateral ratio after the mint
+}
+
+/// Result of quote_burn, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BurnQuote {
+    pub stablecoin_amount: u64,     // Stablecoins burned
+    pub bond_amount: u64,           // Bonds returned from the position
+    pub fee: u64,                   // Fee charged to the burner, in lamports
+    pub bond_price: u64,            // Price used, scaled by PRICE_SCALE
+    pub position_ratio: u64,        // Burner's collateral ratio after the burn
+    pub pool_ratio: u64,            // Stablecoin's collateral ratio after the burn
+}
+
+/// Result of quote_yield, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct YieldQuote {
+    pub user_yield: u64,            // Yield minted to the position holder
+    pub protocol_fee: u64,          // Yield minted to the protocol fee vault
+    pub yield_price: u64,           // Conversion-adjusted price used for accrual
+    pub bond_price: u64,            // Bond price in the target currency, scaled by PRICE_SCALE
+    pub position_ratio: u64,        // Holder's collateral ratio, unchanged by yield
+    pub next_rebase_at: i64,        // Earliest time distribute_yield will accept
+}
+
+/// Result of quote_flow_capacity, returned via return data. Capacity is what
+/// can still flow before a cap is hit; no cap reports u64::MAX.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FlowCapacity {
+    pub mint_remaining: u64,        // Stablecoin-wide net mint capacity
+    pub redeem_remaining: u64,      // Stablecoin-wide net redeem capacity
+    pub user_mint_remaining: u64,   // Holder's net mint capacity
+    pub user_redeem_remaining: u64, // Holder's net redeem capacity
+    pub window_ends_at: i64,        // When the stablecoin-wide window resets, 0 if none is open
+    pub user_window_ends_at: i64,   // When the holder's window resets, 0 if none is open
+}
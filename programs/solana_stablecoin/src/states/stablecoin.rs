@@ -1,6 +1,56 @@
 // states/stablecoin_state.rs
 use anchor_lang::prelude::*;
-use crate::states::user::UserShare;
+use crate::states::{factory_state::FactoryState, user::UserShare, protocol_params::StablecoinParamOverrides, flow_limits::{FlowLimits, FlowWindow}};
+use crate::errors::StablecoinError;
+use crate::constants::*;
+use crate::math::{mul_div, mul_div_u64, to_u64, Fixed, Rounding};
+
+/// Which positions a peg redemption draws collateral from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedemptionMode {
+    LowestHealth,   // Riskiest positions first, Liquity-style
+    ProRata,        // Every position contributes in proportion to its debt
+}
+
+/// Whether holders must pass Etherfuse KYC to mint, burn and redeem
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum KycPolicy {
+    Required,       // Holder needs an active Etherfuse KYC record
+    NotRequired,    // Open to any wallet
+}
+
+/// Tier and jurisdiction requirements a stablecoin places on holders under
+/// KycPolicy::Required, checked against their KycAttestation. The defaults,
+/// tier 0 and no jurisdictions, only need the Etherfuse approval.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct KycRequirements {
+    pub min_tier: u8,
+    pub jurisdictions: [[u8; 2]; MAX_KYC_JURISDICTIONS], // ISO 3166-1 alpha-2 codes, unused slots zeroed
+}
+
+impl KycRequirements {
+    /// Whether holders need a KycAttestation at all
+    pub fn needs_attestation(&self) -> bool {
+        self.min_tier > 0 || self.restricts_jurisdiction()
+    }
+
+    pub fn restricts_jurisdiction(&self) -> bool {
+        self.jurisdictions.iter().any(|code| *code != [0; 2])
+    }
+
+    pub fn allows_jurisdiction(&self, jurisdiction: &[u8; 2]) -> bool {
+        !self.restricts_jurisdiction() || self.jurisdictions.contains(jurisdiction)
+    }
+}
+
+/// Who may mint a stablecoin, on top of its KYC policy. Burning and
+/// redemption stay open to every holder.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum MintPolicy {
+    Open,           // Anyone the KYC policy admits
+    KycOnly,        // Minters need an active KYC record even if the KYC policy waives it
+    AllowlistOnly,  // Minters need an allowlist entry from the stablecoin authority
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -14,8 +64,12 @@ pub struct StablecoinState {
     pub original_symbol: String,
     #[max_len(10)]
     pub target_currency: String,
+    #[max_len(200)]
+    pub uri: String,                    // Metaplex metadata URI
+    pub factory: Pubkey,                // Factory this stablecoin belongs to
     pub creator: Pubkey,
     pub mint: Pubkey,
+    pub token_program: Pubkey,          // SPL Token or Token-2022 program owning the mint
     pub last_updated: i64,
 
     // User info
@@ -41,17 +95,652 @@ pub struct StablecoinState {
 
     // Bond info
     pub bond_mint: Pubkey,
+
+    // Decimal normalization, 10^decimals of each mint
+    pub bond_scale: u64,
+    pub stablecoin_scale: u64,
+    pub yield_scale: u64,
     
     // Price feeds
-    pub fiat_oracle: Pubkey,            // Switchboard oracle for fiat price
-    pub last_price_update: i64,         // Last time prices were checked
+    pub fiat_oracle: Pubkey,            // Switchboard FX feed into target currency, default if none
     
     // Protocol parameters
-    pub is_paused: bool,
+    pub paused_by_authority: bool,      // Paused by the coin's authority or pauser
+    pub paused_by_factory: bool,        // Paused by the factory guardian; only it can lift this
     pub fee_rate: u16,                  // In basis points
     pub last_fee_collection: i64,
+    pub stability_fee_bps: u16,         // Annual stability fee on outstanding debt
+    pub stability_fee_index: u128,      // Cumulative debt growth from the fee, scaled by RATE_INDEX_SCALE
+    pub last_stability_fee_accrual: i64,
+    pub total_stability_fees: u64,      // Stability fees minted to the fee vault
+    pub param_overrides: StablecoinParamOverrides, // Per-coin overrides of factory protocol params
+    pub kyc_policy: KycPolicy,          // KYC gate on mint, burn and redeem
+    pub compliance_mode: bool,          // Stablecoin PDA is the mint's freeze authority
+    pub mint_policy: MintPolicy,        // Extra gate on minting
+    pub flow_limits: FlowLimits,        // Caps on net mint and redemption per window
+    pub flow_window: FlowWindow,        // Flow across all holders in the current window
+
+    // Global settlement
+    pub is_shutdown: bool,              // Permanently wound down; only settlement remains
+    pub settlement_price: u64,          // Bond price frozen at shutdown, scaled by PRICE_SCALE; informational
+    pub settlement_rate: u64,           // Bonds per stablecoin frozen at shutdown, scaled by PRICE_SCALE
+    pub shutdown_at: i64,
     
     // Administrative
+    pub authority: Pubkey,                  // Manages the coin; distinct from the seed-bound creator
+    pub pending_authority: Option<Pubkey>,  // Proposed authority awaiting acceptance
+    pub metadata_editor: Option<Pubkey>,    // Delegate allowed to update name, symbol and uri
+    pub pauser: Option<Pubkey>,             // Delegate allowed to pause and resume
+    pub compliance_officer: Option<Pubkey>, // Delegate allowed to denylist, freeze and thaw holders
+    pub schema_version: u16,                // Account layout version, see STABLECOIN_SCHEMA_VERSION
     pub bump: u8,
-    pub reserved: [u8; 32],
-}
\ No newline at end of file
+    pub kyc_requirements: KycRequirements,  // Tier and jurisdiction gate under KycPolicy::Required
+    pub reserved: [u8; 15],
+}
+
+impl StablecoinState {
+    /// Normalization factor for a mint with `decimals` decimal places
+    pub fn decimals_scale(decimals: u8) -> Result<u64> {
+        10u64
+            .checked_pow(decimals as u32)
+            .ok_or(StablecoinError::MathOverflow.into())
+    }
+
+    /// Whether the account has been migrated to the current layout
+    pub fn is_current(&self) -> bool {
+        self.schema_version == STABLECOIN_SCHEMA_VERSION
+    }
+
+    /// The authority or its delegated metadata editor
+    pub fn can_edit_metadata(&self, key: &Pubkey) -> bool {
+        self.authority == *key || self.metadata_editor == Some(*key)
+    }
+
+    /// Max oracle age for this coin: its override or the factory setting
+    pub fn oracle_staleness_threshold(&self, factory_state: &FactoryState) -> i64 {
+        self.param_overrides.oracle_staleness_threshold
+            .unwrap_or(factory_state.protocol_params.oracle_staleness_threshold)
+    }
+
+    /// Peg redemption fee for this coin: its override or the factory setting
+    pub fn redemption_fee_bps(&self, factory_state: &FactoryState) -> u16 {
+        self.param_overrides.redemption_fee_bps
+            .unwrap_or(factory_state.protocol_params.redemption_fee_bps)
+    }
+
+    /// A stablecoin is paused while either its authority or the factory holds a pause
+    pub fn is_paused(&self) -> bool {
+        self.paused_by_authority || self.paused_by_factory
+    }
+
+    /// The authority or its delegated pauser
+    pub fn can_pause(&self, key: &Pubkey) -> bool {
+        self.authority == *key || self.pauser == Some(*key)
+    }
+
+    /// The authority or its delegated compliance officer
+    pub fn can_enforce_compliance(&self, key: &Pubkey) -> bool {
+        self.authority == *key || self.compliance_officer == Some(*key)
+    }
+
+    /// Fee rate in basis points charged on mint and burn: the stablecoin's own
+    /// fee layered on top of the bond's custom fee (or the factory base fee).
+    pub fn effective_fee_rate(&self, factory_state: &FactoryState) -> Result<u16> {
+        let bond_fee_rate = factory_state.get_fee_rate(&self.bond_mint)?;
+
+        let fee_rate = bond_fee_rate
+            .checked_add(self.fee_rate)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        Ok(fee_rate.min(MAX_FEE_RATE_BPS))
+    }
+
+    /// Fee charged on a mint or burn of `amount` at the effective fee rate,
+    /// rounded up in the protocol's favor
+    pub fn fee_amount(&self, factory_state: &FactoryState, amount: u64) -> Result<u64> {
+        let fee_rate = self.effective_fee_rate(factory_state)?;
+
+        mul_div_u64(amount, fee_rate as u64, BPS_SCALE as u64, Rounding::Up)
+            .map_err(|_| StablecoinError::FeeTooLarge.into())
+    }
+
+    /// Bond base units worth `value` stablecoin base units at `bond_price`
+    /// (scaled by PRICE_SCALE)
+    pub fn bonds_for_value(&self, value: u64, bond_price: u64, rounding: Rounding) -> Result<u128> {
+        require!(bond_price > 0, StablecoinError::InvalidPrice);
+
+        let denominator = (bond_price as u128)
+            .checked_mul(self.stablecoin_scale as u128)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        mul_div(
+            value as u128 * PRICE_SCALE,
+            self.bond_scale as u128,
+            denominator,
+            rounding,
+        )
+    }
+
+    /// Stablecoin base units that `bond_amount` bond base units are worth at
+    /// `bond_price` (scaled by PRICE_SCALE), the inverse of bonds_for_value
+    pub fn value_of_bonds(&self, bond_amount: u64, bond_price: u64, rounding: Rounding) -> Result<u64> {
+        let gross = (bond_amount as u128)
+            .checked_mul(bond_price as u128)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        to_u64(mul_div(
+            gross,
+            self.stablecoin_scale as u128,
+            PRICE_SCALE * self.bond_scale as u128,
+            rounding,
+        )?)
+    }
+
+    /// Collateral ratio in basis points of `bond_amount` bonds backing `debt`
+    /// stablecoins at `bond_price`, rounded down. No debt reports u64::MAX.
+    pub fn collateral_ratio_for(&self, bond_amount: u64, debt: u64, bond_price: u64) -> Result<u64> {
+        if debt == 0 {
+            return Ok(u64::MAX);
+        }
+
+        let gross = (bond_amount as u128)
+            .checked_mul(bond_price as u128)
+            .ok_or(StablecoinError::MathOverflow)?
+            .checked_mul(self.stablecoin_scale as u128)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        let denominator = PRICE_SCALE
+            .checked_mul(self.bond_scale as u128)
+            .and_then(|scaled| scaled.checked_mul(debt as u128))
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        let ratio = mul_div(gross, BPS_SCALE as u128, denominator, Rounding::Down)?;
+
+        Ok(ratio.min(u64::MAX as u128) as u64)
+    }
+
+    /// Collateral ratio of a position in basis points at `bond_price`
+    pub fn position_ratio(&self, share: &UserShare, bond_price: u64) -> Result<u64> {
+        self.collateral_ratio_for(share.bond_amount, share.mint_amount, bond_price)
+    }
+
+    /// Bonds needed to back `debt` at the stablecoin's collateral ratio,
+    /// rounded up in the protocol's favor
+    pub fn required_collateral(&self, debt: u64, bond_price: u64) -> Result<u64> {
+        let required_value = mul_div_u64(
+            debt,
+            self.collateral_ratio as u64,
+            BPS_SCALE as u64,
+            Rounding::Up,
+        )?;
+
+        to_u64(self.bonds_for_value(required_value, bond_price, Rounding::Up)?)
+    }
+
+    /// Stablecoins minted against `bond_amount` bonds at `bond_price` (scaled by
+    /// PRICE_SCALE): collateral_value * BPS_SCALE / collateral_ratio, rounded down
+    pub fn mint_amount_for(&self, bond_amount: u64, bond_price: u64) -> Result<u64> {
+        let gross = (bond_amount as u128)
+            .checked_mul(bond_price as u128)
+            .ok_or(StablecoinError::MathOverflow)?
+            .checked_mul(self.stablecoin_scale as u128)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        let denominator = PRICE_SCALE
+            .checked_mul(self.bond_scale as u128)
+            .and_then(|scaled| scaled.checked_mul(self.collateral_ratio as u128))
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        let mint_amount = mul_div(gross, BPS_SCALE as u128, denominator, Rounding::Down)?;
+
+        require!(
+            mint_amount <= u64::MAX as u128,
+            StablecoinError::ExcessivePriceDeviation
+        );
+
+        Ok(mint_amount as u64)
+    }
+
+    /// Grows the stability fee index to `now` and adds each position's share of
+    /// the fee to its debt and to total supply. Returns the fee, which the
+    /// caller must mint to the fee vault. Growth and per-position fees round up
+    /// so that small positions can't accrue forever without paying. Accrual
+    /// runs at most once per STABILITY_FEE_ACCRUAL_INTERVAL, which bounds the
+    /// rounding to one base unit per position per interval; calls in between
+    /// leave the accrual clock untouched.
+    pub fn accrue_stability_fee(&mut self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.last_stability_fee_accrual);
+        if elapsed < STABILITY_FEE_ACCRUAL_INTERVAL && self.stability_fee_bps != 0 && !self.is_shutdown {
+            return Ok(0);
+        }
+
+        self.accrue_stability_fee_through(now)
+    }
+
+    /// Accrues the stability fee through `now` regardless of the accrual
+    /// interval, so that a rate change charges the time before it at the old rate
+    pub fn accrue_stability_fee_through(&mut self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.last_stability_fee_accrual);
+        if elapsed <= 0 {
+            return Ok(0);
+        }
+
+        if self.stability_fee_bps == 0 || self.is_shutdown {
+            self.last_stability_fee_accrual = now;
+            return Ok(0);
+        }
+        self.last_stability_fee_accrual = now;
+
+        let growth = mul_div(
+            self.stability_fee_index,
+            self.stability_fee_bps as u128 * elapsed as u128,
+            BPS_SCALE as u128 * YEAR_IN_SECONDS as u128,
+            Rounding::Up,
+        )?;
+
+        let mut total_fee: u64 = 0;
+        for share in self.user_shares.iter_mut() {
+            let fee = to_u64(mul_div(
+                share.mint_amount as u128,
+                growth,
+                self.stability_fee_index,
+                Rounding::Up,
+            )?)?;
+
+            share.mint_amount = share.mint_amount
+                .checked_add(fee)
+                .ok_or(StablecoinError::MathOverflow)?;
+
+            total_fee = total_fee
+                .checked_add(fee)
+                .ok_or(StablecoinError::MathOverflow)?;
+        }
+
+        self.stability_fee_index = self.stability_fee_index
+            .checked_add(growth)
+            .ok_or(StablecoinError::MathOverflow)?;
+        self.total_supply = self.total_supply
+            .checked_add(total_fee)
+            .ok_or(StablecoinError::MathOverflow)?;
+        self.total_stability_fees = self.total_stability_fees
+            .checked_add(total_fee)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        Ok(total_fee)
+    }
+
+    /// Stablecoins minted and the fee charged for depositing `bond_amount` at
+    /// `bond_price`. Shared by mint_tokens and quote_mint.
+    pub fn price_mint(&self, factory_state: &FactoryState, bond_amount: u64, bond_price: u64) -> Result<(u64, u64)> {
+        let bond_config = factory_state.get_bond_config(&self.bond_mint)
+            .ok_or(StablecoinError::BondNotFound)?;
+
+        require!(
+            bond_amount >= bond_config.min_creation_amount,
+            StablecoinError::DepositTooSmall
+        );
+
+        let mint_amount = self.mint_amount_for(bond_amount, bond_price)?;
+        let fee = self.fee_amount(factory_state, mint_amount)?;
+        Ok((mint_amount, fee))
+    }
+
+    /// Bonds released from `owner`'s position and the fee charged for burning
+    /// `stablecoin_amount`. Shared by burn_tokens and quote_burn.
+    pub fn price_burn(&self, factory_state: &FactoryState, owner: &Pubkey, stablecoin_amount: u64) -> Result<(u64, u64)> {
+        let bond_config = factory_state.get_bond_config(&self.bond_mint)
+            .ok_or(StablecoinError::BondNotFound)?;
+
+        require!(
+            stablecoin_amount >= bond_config.min_redemption_amount,
+            StablecoinError::RedeemAmountTooSmall
+        );
+
+        let bond_amount = self.bond_return_for(owner, stablecoin_amount)?;
+        let fee = self.fee_amount(factory_state, stablecoin_amount)?;
+        Ok((bond_amount, fee))
+    }
+
+    /// Bonds released from `owner`'s position when burning `stablecoin_amount`.
+    /// Redemption is proportional to what the owner deposited, independent of the
+    /// current oracle price: bond_amount * stablecoin_amount / mint_amount,
+    /// rounded down so the position keeps any remainder
+    pub fn bond_return_for(&self, owner: &Pubkey, stablecoin_amount: u64) -> Result<u64> {
+        let share = self.user_shares
+            .iter()
+            .find(|s| s.owner == *owner)
+            .ok_or(StablecoinError::UserShareNotFound)?;
+
+        require!(
+            share.mint_amount >= stablecoin_amount,
+            StablecoinError::InsufficientUserShare
+        );
+
+        mul_div_u64(share.bond_amount, stablecoin_amount, share.mint_amount, Rounding::Down)
+    }
+
+    /// Yield accrued by `owner`'s position since the last rebase at `current_price`
+    /// (scaled by PRICE_SCALE), split into (protocol_fee, user_yield)
+    pub fn rebase_yield(
+        &self,
+        factory_state: &FactoryState,
+        owner: &Pubkey,
+        current_price: u128,
+        current_time: i64,
+    ) -> Result<(u64, u64)> {
+        let time_elapsed = (current_time - self.last_rebase) as u128;
+
+        let user_share = self.user_shares
+            .iter()
+            .find(|share| share.owner == *owner)
+            .ok_or(StablecoinError::NoUserPosition)?;
+
+        // Yield per stablecoin over the elapsed time, kept fractional
+        let yield_rate = Fixed::from_ratio(
+            current_price
+                .checked_mul(time_elapsed)
+                .ok_or(StablecoinError::MathOverflow)?,
+            YEAR_IN_SECONDS as u128 * PRICE_SCALE,
+            Rounding::Down,
+        )?;
+
+        // Yield accrues on stablecoin units and is paid in yield mint units
+        let total_yield = yield_rate
+            .mul_int(user_share.mint_amount as u128)?
+            .mul_int(self.yield_scale as u128)?
+            .div_int(self.stablecoin_scale as u128, Rounding::Down)?
+            .to_u64(Rounding::Down)?;
+
+        let protocol_fee = mul_div_u64(
+            total_yield,
+            factory_state.protocol_params.protocol_fee_bps as u64,
+            BPS_SCALE as u64,
+            Rounding::Up,
+        )?;
+
+        let user_yield = total_yield
+            .checked_sub(protocol_fee)
+            .ok_or(StablecoinError::MathOverflow)?;
+
+        Ok((protocol_fee, user_yield))
+    }
+
+    /// Sum of the debt across all positions
+    pub fn total_position_debt(&self) -> Result<u128> {
+        self.user_shares
+            .iter()
+            .try_fold(0u128, |acc, s| acc.checked_add(s.mint_amount as u128))
+            .ok_or(StablecoinError::MathOverflow.into())
+    }
+
+    /// Splits `stablecoin_amount` of debt across positions in proportion to
+    /// their debt. Shares round down and the dust goes to the last positions
+    /// with room for it, so the parts add up to exactly `stablecoin_amount`
+    /// and position debt keeps summing to total supply.
+    fn pro_rata_debt_reductions(&self, stablecoin_amount: u64, total_debt: u128) -> Result<Vec<u64>> {
+        let mut reductions = self.user_shares
+            .iter()
+            .map(|share| to_u64(mul_div(
+                share.mint_amount as u128,
+                stablecoin_amount as u128,
+                total_debt,
+                Rounding::Down,
+            )?))
+            .collect::<Result<Vec<u64>>>()?;
+
+        // Each share rounds down, so the parts never exceed the amount
+        let mut remainder = stablecoin_amount - reductions.iter().sum::<u64>();
+        for (reduction, share) in reductions.iter_mut().zip(&self.user_shares).rev() {
+            if remainder == 0 {
+                break;
+            }
+            let extra = remainder.min(share.mint_amount - *reduction);
+            *reduction += extra;
+            remainder -= extra;
+        }
+
+        Ok(reductions)
+    }
+
+    /// Redeems `stablecoin_amount` at face value against positions, paying out
+    /// bonds worth the redeemed amount at `bond_price` (scaled by PRICE_SCALE).
+    /// Each position's debt is cancelled by what it pays; underwater positions
+    /// give up all of their collateral. Returns the gross bonds released.
+    pub fn redeem_against_positions(
+        &mut self,
+        stablecoin_amount: u64,
+        bond_price: u64,
+        mode: RedemptionMode,
+        now: i64,
+    ) -> Result<u64> {
+        require!(bond_price > 0, StablecoinError::InvalidPrice);
+
+        let total_debt = self.total_position_debt()?;
+
+        require!(
+            stablecoin_amount as u128 <= total_debt,
+            StablecoinError::InsufficientCollateral
+        );
+
+        // Work out how much debt each position cancels
+        let mut debt_reductions = vec![0u64; self.user_shares.len()];
+        match mode {
+            RedemptionMode::LowestHealth => {
+                let mut order = Vec::with_capacity(self.user_shares.len());
+                for (index, share) in self.user_shares.iter().enumerate() {
+                    if share.mint_amount > 0 {
+                        order.push((self.position_ratio(share, bond_price)?, index));
+                    }
+                }
+                order.sort_unstable();
+
+                let mut remaining = stablecoin_amount;
+                for (_, index) in order {
+                    if remaining == 0 {
+                        break;
+                    }
+                    let taken = remaining.min(self.user_shares[index].mint_amount);
+                    debt_reductions[index] = taken;
+                    remaining -= taken;
+                }
+            }
+            RedemptionMode::ProRata => {
+                debt_reductions = self.pro_rata_debt_reductions(stablecoin_amount, total_debt)?;
+            }
+        }
+
+        // Face value of each position's cancelled debt in bonds
+        let face_values = debt_reductions
+            .iter()
+            .map(|debt| self.bonds_for_value(*debt, bond_price, Rounding::Down))
+            .collect::<Result<Vec<u128>>>()?;
+
+        let mut bonds_released: u64 = 0;
+
+        for ((share, debt_reduction), face_value) in self.user_shares
+            .iter_mut()
+            .zip(debt_reductions)
+            .zip(face_values)
+        {
+            if debt_reduction == 0 {
+                continue;
+            }
+
+            // Capped by the position's collateral
+            let bond_reduction = face_value.min(share.bond_amount as u128) as u64;
+
+            share.mint_amount -= debt_reduction;
+            share.bond_amount -= bond_reduction;
+            share.timestamp = now;
+
+            bonds_released = bonds_released
+                .checked_add(bond_reduction)
+                .ok_or(StablecoinError::MathOverflow)?;
+        }
+
+        // Drop positions that have been fully redeemed
+        self.user_shares.retain(|s| s.bond_amount > 0 || s.mint_amount > 0);
+
+        Ok(bonds_released)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: u64 = 1_000_000;
+    const PRICE: u64 = 1_000_000;
+
+    /// A USD coin at 150% with 6-decimal mints and the given (bonds, debt)
+    /// positions
+    fn state_with(positions: &[(u64, u64)]) -> StablecoinState {
+        let zeroed = vec![0u8; StablecoinState::INIT_SPACE];
+        let mut state = StablecoinState::deserialize(&mut zeroed.as_slice()).unwrap();
+        state.collateral_ratio = 15_000;
+        state.bond_scale = ONE;
+        state.stablecoin_scale = ONE;
+        state.user_shares = positions
+            .iter()
+            .map(|&(bond_amount, mint_amount)| UserShare {
+                owner: Pubkey::new_unique(),
+                bond_amount,
+                mint_amount,
+                timestamp: 0,
+            })
+            .collect();
+        state.total_supply = positions.iter().map(|&(_, debt)| debt).sum();
+        state
+    }
+
+    #[test]
+    fn lowest_health_redeems_the_riskiest_position_first() {
+        let mut state = state_with(&[(400 * ONE, 200 * ONE), (300 * ONE, 200 * ONE)]);
+        let safer = state.user_shares[0].owner;
+
+        let released = state
+            .redeem_against_positions(150 * ONE, PRICE, RedemptionMode::LowestHealth, 7)
+            .unwrap();
+
+        assert_eq!(released, 150 * ONE);
+        assert_eq!(state.user_shares[0].owner, safer);
+        assert_eq!((state.user_shares[0].bond_amount, state.user_shares[0].mint_amount), (400 * ONE, 200 * ONE));
+        assert_eq!((state.user_shares[1].bond_amount, state.user_shares[1].mint_amount), (150 * ONE, 50 * ONE));
+        assert_eq!(state.user_shares[1].timestamp, 7);
+    }
+
+    #[test]
+    fn lowest_health_spills_into_the_next_position() {
+        let mut state = state_with(&[(400 * ONE, 200 * ONE), (300 * ONE, 200 * ONE)]);
+
+        let released = state
+            .redeem_against_positions(250 * ONE, PRICE, RedemptionMode::LowestHealth, 0)
+            .unwrap();
+
+        // The riskier position is fully redeemed and keeps its surplus bonds
+        assert_eq!(released, 250 * ONE);
+        assert_eq!((state.user_shares[1].bond_amount, state.user_shares[1].mint_amount), (100 * ONE, 0));
+        assert_eq!((state.user_shares[0].bond_amount, state.user_shares[0].mint_amount), (350 * ONE, 150 * ONE));
+    }
+
+    #[test]
+    fn underwater_positions_give_up_all_their_collateral() {
+        let mut state = state_with(&[(100 * ONE, 200 * ONE)]);
+
+        let released = state
+            .redeem_against_positions(200 * ONE, PRICE, RedemptionMode::LowestHealth, 0)
+            .unwrap();
+
+        assert_eq!(released, 100 * ONE);
+        assert!(state.user_shares.is_empty());
+    }
+
+    #[test]
+    fn redemptions_cannot_exceed_position_debt() {
+        let mut state = state_with(&[(300 * ONE, 200 * ONE)]);
+
+        let result = state.redeem_against_positions(200 * ONE + 1, PRICE, RedemptionMode::ProRata, 0);
+        assert_eq!(result.unwrap_err(), StablecoinError::InsufficientCollateral.into());
+    }
+
+    #[test]
+    fn pro_rata_rounding_dust_keeps_debt_matching_supply() {
+        let amount = 5;
+
+        let mut state = state_with(&[(30, 3), (30, 3), (40, 4)]);
+        state.redeem_against_positions(amount, PRICE, RedemptionMode::ProRata, 0).unwrap();
+        let debts: Vec<u64> = state.user_shares.iter().map(|s| s.mint_amount).collect();
+        assert_eq!(debts, vec![2, 2, 1]);
+        assert_eq!(debts.iter().sum::<u64>(), state.total_supply - amount);
+    }
+
+    #[test]
+    fn pro_rata_dust_skips_positions_without_room() {
+        // Shares of 4, 4 and 0 leave 2 of dust; the last position can only
+        // take 1 of it, so the middle one takes the rest
+        let mut state = state_with(&[(50, 5), (50, 5), (10, 1)]);
+        state.redeem_against_positions(10, PRICE, RedemptionMode::ProRata, 0).unwrap();
+
+        let debts: Vec<u64> = state.user_shares.iter().map(|s| s.mint_amount).collect();
+        assert_eq!(debts, vec![1, 0, 0]);
+    }
+
+    /// `state_with` charging a 5% stability fee from time zero
+    fn accruing(positions: &[(u64, u64)]) -> StablecoinState {
+        let mut state = state_with(positions);
+        state.stability_fee_bps = 500;
+        state.stability_fee_index = RATE_INDEX_SCALE;
+        state
+    }
+
+    #[test]
+    fn stability_fee_accrues_at_the_annual_rate() {
+        let mut state = accruing(&[(1_500 * ONE, 1_000 * ONE)]);
+
+        let fee = state.accrue_stability_fee(YEAR_IN_SECONDS).unwrap();
+
+        // Rounding up costs at most a base unit
+        assert!((50 * ONE..=50 * ONE + 1).contains(&fee));
+        assert_eq!(state.user_shares[0].mint_amount, 1_000 * ONE + fee);
+        assert_eq!(state.total_supply, 1_000 * ONE + fee);
+        assert_eq!(state.total_stability_fees, fee);
+        assert_eq!(state.stability_fee_index, RATE_INDEX_SCALE * 105 / 100);
+    }
+
+    #[test]
+    fn small_positions_still_pay_the_stability_fee() {
+        let mut state = accruing(&[(3, 2), (1_500 * ONE, 1_000 * ONE)]);
+
+        let fee = state.accrue_stability_fee(STABILITY_FEE_ACCRUAL_INTERVAL).unwrap();
+
+        assert_eq!(state.user_shares[0].mint_amount, 3);
+        assert!(state.stability_fee_index > RATE_INDEX_SCALE);
+        assert_eq!(fee, state.total_supply - 1_000 * ONE - 2);
+    }
+
+    #[test]
+    fn stability_fee_waits_for_a_full_interval() {
+        let mut state = accruing(&[(1_500 * ONE, 1_000 * ONE)]);
+
+        assert_eq!(state.accrue_stability_fee(STABILITY_FEE_ACCRUAL_INTERVAL - 1).unwrap(), 0);
+        assert_eq!(state.last_stability_fee_accrual, 0);
+        assert_eq!(state.stability_fee_index, RATE_INDEX_SCALE);
+
+        // The skipped time is charged by the next accrual
+        let fee = state.accrue_stability_fee(2 * STABILITY_FEE_ACCRUAL_INTERVAL).unwrap();
+        let expected = 1_000 * ONE as u128 * 500 * 2 * STABILITY_FEE_ACCRUAL_INTERVAL as u128
+            / (BPS_SCALE as u128 * YEAR_IN_SECONDS as u128);
+        assert!(fee as u128 >= expected && fee as u128 <= expected + 1);
+        assert_eq!(state.last_stability_fee_accrual, 2 * STABILITY_FEE_ACCRUAL_INTERVAL);
+    }
+
+    #[test]
+    fn shut_down_coins_accrue_no_stability_fee() {
+        let mut state = accruing(&[(1_500 * ONE, 1_000 * ONE)]);
+        state.is_shutdown = true;
+
+        assert_eq!(state.accrue_stability_fee(YEAR_IN_SECONDS).unwrap(), 0);
+        assert_eq!(state.last_stability_fee_accrual, YEAR_IN_SECONDS);
+        assert_eq!(state.total_supply, 1_000 * ONE);
+    }
+}
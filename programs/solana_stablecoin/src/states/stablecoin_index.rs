@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// StablecoinIndex maps a sequential index to a stablecoin so clients can
+/// enumerate every coin in the factory by walking 0..stablecoin_count.
+#[account]
+#[derive(InitSpace)]
+pub struct StablecoinIndex {
+    pub factory: Pubkey,
+    pub index: u32,
+    pub stablecoin_state: Pubkey,
+    pub mint: Pubkey,
+    pub bond_mint: Pubkey,
+    pub creator: Pubkey,
+    pub bump: u8,
+}
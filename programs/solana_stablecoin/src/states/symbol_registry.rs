@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::errors::StablecoinError;
+
+/// SymbolRecord claims a stablecoin symbol factory-wide. The PDA is keyed by the
+/// symbol itself, so a second claim on the same symbol fails at account creation.
+#[account]
+#[derive(InitSpace)]
+pub struct SymbolRecord {
+    #[max_len(10)]
+    pub symbol: String,
+    pub stablecoin: Pubkey,         // Owning stablecoin, default while reserved
+    pub is_reserved: bool,          // Held back by the factory admin
+    pub bump: u8,
+}
+
+impl SymbolRecord {
+    /// Symbols are 1-10 uppercase ASCII letters or digits, which is also
+    /// their normalized form for registry lookups
+    pub fn validate_symbol(symbol: &str) -> Result<()> {
+        require!(!symbol.is_empty() && symbol.len() <= 10, StablecoinError::InvalidSymbol);
+        require!(
+            symbol.bytes().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()),
+            StablecoinError::InvalidSymbolFormat
+        );
+        Ok(())
+    }
+
+    /// Uppercases `symbol` and drops everything but ASCII letters and digits,
+    /// for symbols stored before the registry enforced the format
+    pub fn normalize_symbol(symbol: &str) -> String {
+        symbol
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_uppercase())
+            .collect()
+    }
+}
@@ -0,0 +1,12 @@
+// token_config.rs
+use anchor_lang::prelude::*;
+
+/// Token-2022 options for a stablecoin mint. Passing this at creation puts the
+/// mint under the Token-2022 program; omitting it creates a classic SPL mint.
+/// The metadata pointer is always enabled and points at the mint's Metaplex metadata PDA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct Token2022Config {
+    pub permanent_delegate: bool,                // stablecoin PDA as permanent delegate, used by seize_holder
+    pub interest_rate: Option<i16>,              // Interest-bearing extension rate in basis points
+    pub transfer_hook_program: Option<Pubkey>,   // Optional transfer hook program
+}
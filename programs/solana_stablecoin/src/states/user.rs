@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::states::flow_limits::FlowWindow;
 
 #[account]
 #[derive(InitSpace)]
@@ -8,9 +9,11 @@ pub struct UserState {
     pub stablecoin: Pubkey,  // The stablecoin this user state belongs to
     pub total_yield_collected: u64,
     pub last_yield_collection: i64,
-    pub bond_amount: u64,
-    pub stablecoin_amount: u64,
-    pub reserved: [u8; 32],  // Space for future fields
+    /// Space for future fields. The first 16 bytes held the retired per-user
+    /// bond and debt totals, now tracked in StablecoinState.user_shares, and
+    /// may be nonzero in older accounts.
+    pub reserved: [u8; 24],
+    pub flow_window: FlowWindow,  // Holder's flow in the current window, see FlowLimits
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -27,4 +30,4 @@ impl anchor_lang::Space for UserShare {
         8 +    // u64 (bond_amount)
         8 +     // u64 (mint_amount)
         8;      // i64 (timestamp)
-}
\ No newline at end of file
+}
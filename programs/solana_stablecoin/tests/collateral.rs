@@ -0,0 +1,70 @@
+// tests/collateral.rs
+mod common;
+
+use common::*;
+use solana_stablecoin::{
+    errors::StablecoinError,
+    events::{CollateralDeposited, CollateralWithdrawn},
+    states::{StablecoinState, UserState},
+};
+
+#[test]
+fn mint_deposit_withdraw() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (user, bonds) = env.new_user(1_000 * ONE);
+
+    env.mint(&coin, &user, &bonds, 300 * ONE).expect("mint");
+
+    let user_state_key = TestEnv::user_state(&user, &coin);
+    let user_state: UserState = env.state(&user_state_key);
+    let expected_bump = anchor_lang::prelude::Pubkey::find_program_address(
+        &[b"user_state", user.as_ref(), coin.mint.as_ref()],
+        &solana_stablecoin::ID,
+    ).1;
+    assert_eq!(user_state.bump, expected_bump);
+    assert_eq!(user_state.user, user);
+    assert_eq!(user_state.stablecoin, coin.mint);
+
+    let ix = env.deposit_ix(&coin, &user, &bonds, 100 * ONE);
+    env.process(&ix).expect("deposit");
+    assert_eq!(env.events::<CollateralDeposited>()[0].position_collateral, 400 * ONE);
+    assert_eq!(env.token_balance(&coin.collateral_vault), 400 * ONE);
+
+    // 200 minted at a 150% ratio needs 300 bonds, leaving 100 free
+    let ix = env.withdraw_ix(&coin, &user, &bonds, 100 * ONE);
+    env.process(&ix).expect("withdraw");
+    assert_eq!(env.events::<CollateralWithdrawn>()[0].position_collateral, 300 * ONE);
+    assert_eq!(env.token_balance(&coin.collateral_vault), 300 * ONE);
+    assert_eq!(env.token_balance(&bonds), 700 * ONE);
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.total_collateral, 300 * ONE);
+}
+
+#[test]
+fn withdraw_keeps_the_position_collateralized() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (user, bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &user, &bonds, 300 * ONE).expect("mint");
+
+    let ix = env.withdraw_ix(&coin, &user, &bonds, 1);
+    assert_error(env.process(&ix), StablecoinError::CollateralRatioTooLow);
+}
+
+#[test]
+fn burn_signs_with_the_user_state() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (user, bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &user, &bonds, 300 * ONE).expect("mint");
+
+    let holder_account = TestEnv::user_stablecoin_account(&user, &coin);
+    let minted = env.token_balance(&holder_account);
+    assert!(minted > 0);
+
+    let ix = env.burn_ix(&coin, &user, &bonds, minted / 2);
+    env.process(&ix).expect("burn");
+    assert_eq!(env.token_balance(&holder_account), minted - minted / 2);
+}
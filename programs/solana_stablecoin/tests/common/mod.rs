@@ -0,0 +1,1231 @@
+// tests/common/mod.rs
+//
+// Fixtures shared by the integration tests: a factory with one supported
+// stablebond, helpers to create stablecoins and funded holders, and typed
+// readers for account state and emitted events.
+#![allow(dead_code)]
+
+pub mod runtime;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program_error::ProgramError, program_pack::Pack},
+    system_program, Discriminator, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    metadata::mpl_token_metadata,
+    token::spl_token,
+    token_2022::spl_token_2022,
+};
+use stablebond_sdk::{
+    accounts::{Bond, PaymentFeed},
+    find_bond_pda, find_kyc_pda, find_payment_feed_pda,
+    types::{Discriminator as StablebondDiscriminator, PaymentFeedType},
+};
+use switchboard_solana::{AggregatorAccountData, SwitchboardDecimal};
+
+use solana_stablecoin::{
+    accounts, instruction,
+    constants::*,
+    errors::StablecoinError,
+    states::{
+        factory_state::factory_id_seed, flow_limits::FlowLimits, legacy::StablecoinStateV1,
+        protocol_params::{ProtocolParamsUpdate, StablecoinParamOverrides},
+        stablecoin::{KycPolicy, KycRequirements, MintPolicy, RedemptionMode},
+        token_config::Token2022Config,
+    },
+};
+
+pub use runtime::{Runtime, StoredAccount};
+
+pub const FACTORY_ID: u64 = 1;
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+pub const BOND_DECIMALS: u8 = 6;
+pub const ONE: u64 = 1_000_000;
+
+/// A stablecoin created through the factory
+#[derive(Clone, Copy, Debug)]
+pub struct Coin {
+    pub state: Pubkey,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub token_program: Pubkey,
+    pub fx_oracle: Option<Pubkey>,
+}
+
+pub struct TestEnv {
+    pub rt: Runtime,
+    pub admin: Pubkey,
+    pub factory_state: Pubkey,
+    pub sol_fee_vault: Pubkey,
+    pub fee_mint: Pubkey,
+    pub fee_vault: Pubkey,
+    pub bond_mint: Pubkey,
+    pub bond_info: Pubkey,
+    pub bond_oracle: Pubkey,
+}
+
+impl TestEnv {
+    /// A factory with one supported USD-paying stablebond priced at 1.0
+    pub fn new() -> Self {
+        Self::with_factory_id(FACTORY_ID)
+    }
+
+    /// As `new`, for the factory with `factory_id`. Id 0 is the singleton
+    /// factory that version 1 stablecoins belong to.
+    pub fn with_factory_id(factory_id: u64) -> Self {
+        let rt = Runtime::new();
+        let admin = Pubkey::new_unique();
+        let (factory_state, _) = Pubkey::find_program_address(
+            &[FACTORY_STATE_SEED, factory_id_seed(factory_id).as_ref()],
+            &solana_stablecoin::ID,
+        );
+        let (sol_fee_vault, _) = Pubkey::find_program_address(
+            &[SOL_FEE_VAULT_SEED, factory_id_seed(factory_id).as_ref()],
+            &solana_stablecoin::ID,
+        );
+
+        let fee_mint = Pubkey::new_unique();
+        let bond_mint = Pubkey::new_unique();
+        let (bond_info, _) = find_bond_pda(bond_mint);
+
+        let mut env = TestEnv {
+            rt,
+            admin,
+            factory_state,
+            sol_fee_vault,
+            fee_mint,
+            fee_vault: get_associated_token_address_with_program_id(&factory_state, &fee_mint, &spl_token::ID),
+            bond_mint,
+            bond_info,
+            bond_oracle: Pubkey::new_unique(),
+        };
+
+        env.airdrop(&admin, 100 * LAMPORTS_PER_SOL);
+        env.create_mint(&fee_mint, &Pubkey::new_unique(), 6, &spl_token::ID);
+        env.create_mint(&bond_mint, &Pubkey::new_unique(), BOND_DECIMALS, &spl_token::ID);
+        env.set_bond(PaymentFeedType::SwitchboardUsdcUsd);
+        env.set_oracle(&env.bond_oracle.clone(), 1_000_000, 6);
+
+        env.process(&env.initialize_factory_ix(factory_id, MIN_ALLOWED_COLLATERAL_RATIO, 100))
+            .expect("initialize factory");
+        env.process(&env.add_bond_ix(1, 1)).expect("add bond");
+
+        env
+    }
+
+    // Runtime access
+
+    pub fn process(&mut self, ix: &Instruction) -> std::result::Result<(), ProgramError> {
+        self.rt.process(ix)
+    }
+
+    pub fn now(&self) -> i64 {
+        self.rt.clock.unix_timestamp
+    }
+
+    /// Advances the clock. Feeds keep their round time, see refresh_oracle.
+    pub fn warp(&mut self, seconds: i64) {
+        self.rt.clock.unix_timestamp += seconds;
+        self.rt.clock.slot += (seconds.max(0) as u64) * 2;
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Option<&StoredAccount> {
+        self.rt.accounts.get(key)
+    }
+
+    pub fn exists(&self, key: &Pubkey) -> bool {
+        self.rt.accounts.contains_key(key)
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.account(key).map(|a| a.lamports).unwrap_or_default()
+    }
+
+    pub fn set_account(&mut self, key: &Pubkey, account: StoredAccount) {
+        self.rt.accounts.insert(*key, account);
+    }
+
+    pub fn airdrop(&mut self, key: &Pubkey, lamports: u64) {
+        let account = self.rt.accounts.entry(*key).or_insert_with(|| StoredAccount {
+            owner: system_program::ID,
+            ..StoredAccount::default()
+        });
+        account.lamports += lamports;
+    }
+
+    /// Deserializes an Anchor account
+    pub fn state<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self.account(key).unwrap_or_else(|| panic!("missing account {key}"));
+        T::try_deserialize(&mut account.data.as_slice()).expect("deserialize account")
+    }
+
+    /// Events of type T emitted by the last transaction
+    pub fn events<T: AnchorDeserialize + Discriminator>(&self) -> Vec<T> {
+        self.rt
+            .logs
+            .iter()
+            .filter(|log| log.len() >= 8 && log[..8] == T::DISCRIMINATOR)
+            .map(|log| T::try_from_slice(&log[8..]).expect("decode event"))
+            .collect()
+    }
+
+    /// Decodes what the last transaction returned, e.g. a quote
+    pub fn return_data<T: AnchorDeserialize>(&self) -> T {
+        let (program, data) = self.rt.return_data.as_ref().expect("no return data");
+        assert_eq!(*program, solana_stablecoin::ID);
+        T::try_from_slice(data).expect("decode return data")
+    }
+
+    // Token fixtures
+
+    pub fn create_mint(&mut self, key: &Pubkey, authority: &Pubkey, decimals: u8, token_program: &Pubkey) {
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: Some(*authority).into(),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(key, StoredAccount {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: *token_program,
+            executable: false,
+        });
+    }
+
+    /// A classic SPL token account holding `amount`, crediting the mint's supply
+    pub fn create_token_account(&mut self, key: &Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(key, StoredAccount {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: spl_token::ID,
+            executable: false,
+        });
+
+        let mint_account = self.rt.accounts.get_mut(mint).expect("mint exists");
+        let mut state = spl_token::state::Mint::unpack_from_slice(&mint_account.data).unwrap();
+        state.supply += amount;
+        state.pack_into_slice(&mut mint_account.data);
+    }
+
+    /// Token balance of an SPL Token or Token-2022 account, 0 if it does not exist
+    pub fn token_balance(&self, key: &Pubkey) -> u64 {
+        self.account(key)
+            .map(|account| u64::from_le_bytes(account.data[64..72].try_into().unwrap()))
+            .unwrap_or_default()
+    }
+
+    /// Whether a token account is frozen
+    pub fn is_frozen(&self, key: &Pubkey) -> bool {
+        self.account(key).map(|account| account.data[108] == 2).unwrap_or_default()
+    }
+
+    pub fn mint_supply(&self, key: &Pubkey) -> u64 {
+        self.account(key)
+            .map(|account| u64::from_le_bytes(account.data[36..44].try_into().unwrap()))
+            .unwrap_or_default()
+    }
+
+    /// Moves stablecoins out of a holder's account into `to`'s own wallet
+    /// account as an off-program transfer would, creating it if needed
+    pub fn move_stablecoins(&mut self, coin: &Coin, from: &Pubkey, to: &Pubkey, amount: u64) {
+        let from_account = self.holder_stablecoin_account(from, coin);
+        let to_account = Self::wallet_stablecoin_account(to, coin);
+        if !self.exists(&to_account) {
+            let mut account = self.account(&from_account).expect("sender account").clone();
+            account.data[32..64].copy_from_slice(to.as_ref());
+            account.data[64..72].copy_from_slice(&0u64.to_le_bytes());
+            self.set_account(&to_account, account);
+        }
+
+        for (key, delta) in [(from_account, -(amount as i128)), (to_account, amount as i128)] {
+            let balance = (self.token_balance(&key) as i128 + delta) as u64;
+            self.rt.accounts.get_mut(&key).unwrap().data[64..72].copy_from_slice(&balance.to_le_bytes());
+        }
+    }
+
+    // Etherfuse and Switchboard fixtures
+
+    /// Writes the Etherfuse bond and payment feed records for the bond mint
+    pub fn set_bond(&mut self, payment_feed_type: PaymentFeedType) {
+        let bond = Bond {
+            discriminator: StablebondDiscriminator::Bond,
+            mint: self.bond_mint,
+            version: 1,
+            payment_feed_type: payment_feed_type.clone(),
+            padding0: [0; 3],
+            bond_fee_bps: 0,
+            issuance_number: 1,
+            cutoff_in_seconds: 0,
+        };
+        self.set_account(&self.bond_info.clone(), StoredAccount {
+            lamports: LAMPORTS_PER_SOL,
+            data: bond.try_to_vec().unwrap(),
+            owner: stablebond_sdk::ID,
+            executable: false,
+        });
+
+        let (payment_feed_info, _) = find_payment_feed_pda(payment_feed_type.clone());
+        let feed = PaymentFeed {
+            discriminator: StablebondDiscriminator::PaymentFeed,
+            version: 1,
+            payment_mint: self.fee_mint,
+            payment_decimals: 6,
+            base_price_feed: Pubkey::new_unique(),
+            quote_price_feed: Pubkey::new_unique(),
+            payment_feed_type,
+        };
+        self.set_account(&payment_feed_info, StoredAccount {
+            lamports: LAMPORTS_PER_SOL,
+            data: feed.try_to_vec().unwrap(),
+            owner: stablebond_sdk::ID,
+            executable: false,
+        });
+    }
+
+    /// Writes a Switchboard aggregator whose latest round reports
+    /// mantissa * 10^-scale, opened now
+    pub fn set_oracle(&mut self, key: &Pubkey, mantissa: i128, scale: u32) {
+        self.set_oracle_at(key, mantissa, scale, self.now());
+    }
+
+    pub fn set_oracle_at(&mut self, key: &Pubkey, mantissa: i128, scale: u32, round_open_timestamp: i64) {
+        let mut feed: AggregatorAccountData = unsafe { std::mem::zeroed() };
+        feed.latest_confirmed_round.result = SwitchboardDecimal { mantissa, scale };
+        feed.latest_confirmed_round.round_open_timestamp = round_open_timestamp;
+        feed.latest_confirmed_round.num_success = 1;
+
+        let mut data = AggregatorAccountData::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(unsafe {
+            std::slice::from_raw_parts(
+                &feed as *const AggregatorAccountData as *const u8,
+                std::mem::size_of::<AggregatorAccountData>(),
+            )
+        });
+        self.set_account(key, StoredAccount {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: switchboard_solana::ID,
+            executable: false,
+        });
+    }
+
+    /// Re-opens a feed's round at the current time with the same result
+    pub fn refresh_oracle(&mut self, key: &Pubkey) {
+        let now = self.now();
+        let account = self.rt.accounts.get_mut(key).expect("oracle exists");
+        let feed = unsafe { &mut *(account.data[8..].as_mut_ptr() as *mut AggregatorAccountData) };
+        feed.latest_confirmed_round.round_open_timestamp = now;
+    }
+
+    /// Approves `user` in the Etherfuse KYC registry
+    pub fn set_kyc(&mut self, user: &Pubkey) {
+        let (kyc, _) = find_kyc_pda(*user);
+        self.set_account(&kyc, StoredAccount {
+            lamports: LAMPORTS_PER_SOL,
+            data: vec![StablebondDiscriminator::Kyc as u8, 1],
+            owner: stablebond_sdk::ID,
+            executable: false,
+        });
+    }
+
+    pub fn kyc_pda(user: &Pubkey) -> Pubkey {
+        find_kyc_pda(*user).0
+    }
+
+    /// A funded, KYC-approved holder with `bonds` stablebonds in a token
+    /// account at the returned address
+    pub fn new_user(&mut self, bonds: u64) -> (Pubkey, Pubkey) {
+        let user = Pubkey::new_unique();
+        self.airdrop(&user, 100 * LAMPORTS_PER_SOL);
+        self.set_kyc(&user);
+        let bond_account = Pubkey::new_unique();
+        self.create_token_account(&bond_account, &self.bond_mint.clone(), &user, bonds);
+        (user, bond_account)
+    }
+
+    // Addresses
+
+    pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &solana_stablecoin::ID).0
+    }
+
+    pub fn user_state(user: &Pubkey, coin: &Coin) -> Pubkey {
+        Self::pda(&[USER_STATE_SEED, user.as_ref(), coin.mint.as_ref()])
+    }
+
+    /// The holder's stablecoin account, owned by their user state PDA
+    pub fn user_stablecoin_account(user: &Pubkey, coin: &Coin) -> Pubkey {
+        get_associated_token_address_with_program_id(&Self::user_state(user, coin), &coin.mint, &coin.token_program)
+    }
+
+    /// The holder's own stablecoin account, where transferred coins land
+    pub fn wallet_stablecoin_account(user: &Pubkey, coin: &Coin) -> Pubkey {
+        get_associated_token_address_with_program_id(user, &coin.mint, &coin.token_program)
+    }
+
+    /// The account a holder redeems or settles from: their wallet account if
+    /// they have one, otherwise the one their user state holds
+    pub fn holder_stablecoin_account(&self, user: &Pubkey, coin: &Coin) -> Pubkey {
+        let wallet_account = Self::wallet_stablecoin_account(user, coin);
+        if self.exists(&wallet_account) {
+            wallet_account
+        } else {
+            Self::user_stablecoin_account(user, coin)
+        }
+    }
+
+    pub fn stability_fee_vault(&self, coin: &Coin) -> Pubkey {
+        get_associated_token_address_with_program_id(&self.factory_state, &coin.mint, &coin.token_program)
+    }
+
+    /// The factory's ATA collecting redemption fees in bonds
+    pub fn bond_fee_vault(&self) -> Pubkey {
+        get_associated_token_address_with_program_id(&self.factory_state, &self.bond_mint, &spl_token::ID)
+    }
+
+    pub fn kyc_attestation(&self, user: &Pubkey) -> Pubkey {
+        Self::pda(&[KYC_ATTESTATION_SEED, self.factory_state.as_ref(), user.as_ref()])
+    }
+
+    /// The holder's KYC attestation if the admin has written one
+    pub fn existing_kyc_attestation(&self, user: &Pubkey) -> Option<Pubkey> {
+        Some(self.kyc_attestation(user)).filter(|key| self.exists(key))
+    }
+
+    pub fn denylist_entry(coin: &Coin, user: &Pubkey) -> Pubkey {
+        Self::pda(&[DENYLIST_SEED, coin.state.as_ref(), user.as_ref()])
+    }
+
+    pub fn allowlist_entry(coin: &Coin, user: &Pubkey) -> Pubkey {
+        Self::pda(&[MINTER_ALLOWLIST_SEED, coin.state.as_ref(), user.as_ref()])
+    }
+
+    /// The minter's allowlist entry if the coin's authority has approved them
+    pub fn existing_allowlist_entry(&self, coin: &Coin, user: &Pubkey) -> Option<Pubkey> {
+        Some(Self::allowlist_entry(coin, user)).filter(|key| self.exists(key))
+    }
+
+    pub fn fx_feed(&self, base_currency: &str, quote_currency: &str) -> Pubkey {
+        Self::pda(&[
+            FX_FEED_SEED,
+            self.factory_state.as_ref(),
+            base_currency.as_bytes(),
+            quote_currency.as_bytes(),
+        ])
+    }
+
+    pub fn symbol_record(&self, symbol: &str) -> Pubkey {
+        Self::pda(&[SYMBOL_REGISTRY_SEED, self.factory_state.as_ref(), symbol.as_bytes()])
+    }
+
+    // Instructions
+
+    pub fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+        Instruction {
+            program_id: solana_stablecoin::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        }
+    }
+
+    pub fn initialize_factory_ix(&self, factory_id: u64, min_collateral_ratio: u16, base_fee_rate: u16) -> Instruction {
+        Self::ix(
+            accounts::InitializeFactory {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                fee_mint: self.fee_mint,
+                sol_fee_vault: self.sol_fee_vault,
+                fee_vault: self.fee_vault,
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            },
+            instruction::InitializeFactory { factory_id, min_collateral_ratio, base_fee_rate },
+        )
+    }
+
+    pub fn add_bond_ix(&self, min_creation_amount: u64, min_redemption_amount: u64) -> Instruction {
+        let bond: Bond = Bond::try_from_slice(&self.account(&self.bond_info).unwrap().data).unwrap();
+        Self::ix(
+            accounts::AddSupportedBond {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                bond_mint: self.bond_mint,
+                bond_info: self.bond_info,
+                payment_feed_info: find_payment_feed_pda(bond.payment_feed_type).0,
+                system_program: system_program::ID,
+            },
+            instruction::AddSupportedBond { min_creation_amount, min_redemption_amount },
+        )
+    }
+
+    pub fn set_fx_feed_ix(&self, base_currency: &str, quote_currency: &str, aggregator: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::SetFxFeed {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                fx_feed: self.fx_feed(base_currency, quote_currency),
+                aggregator: *aggregator,
+                system_program: system_program::ID,
+            },
+            instruction::SetFxFeed {
+                base_currency: base_currency.to_string(),
+                quote_currency: quote_currency.to_string(),
+            },
+        )
+    }
+
+    /// Creates a USD stablecoin over the factory's bond
+    /// Writes a stablecoin account in the version 1 layout, as deployed before
+    /// schema versioning and the symbol registry
+    pub fn create_v1_stablecoin(&mut self, symbol: &str) -> Coin {
+        let creator = Pubkey::new_unique();
+        let (state, bump) = Pubkey::find_program_address(
+            &[STABLECOIN_SEED, creator.as_ref(), symbol.as_bytes()],
+            &solana_stablecoin::ID,
+        );
+        let coin = Coin {
+            state,
+            mint: Pubkey::new_unique(),
+            creator,
+            collateral_vault: get_associated_token_address_with_program_id(&state, &self.bond_mint, &spl_token::ID),
+            token_program: spl_token::ID,
+            fx_oracle: None,
+        };
+
+        let legacy = StablecoinStateV1 {
+            name: format!("{symbol} Dollar"),
+            symbol: symbol.to_string(),
+            original_symbol: symbol.to_string(),
+            target_currency: "USD".to_string(),
+            creator,
+            mint: coin.mint,
+            last_updated: self.now(),
+            user_shares: Vec::new(),
+            stablebond_mint: self.bond_mint,
+            collateral_vault: coin.collateral_vault,
+            collateral_ratio: DEFAULT_COLLATERAL_RATIO,
+            total_supply: 0,
+            total_collateral: 0,
+            last_rebase: 0,
+            total_rebase_amount: 0,
+            last_yield_collection: 0,
+            last_rate_update: 0,
+            yield_mint: self.fee_mint,
+            total_yield_collected: 0,
+            bond_mint: self.bond_mint,
+            fiat_oracle: Pubkey::default(),
+            last_price_update: 0,
+            is_paused: false,
+            fee_rate: 100,
+            last_fee_collection: 0,
+            bump,
+            reserved: [0; 32],
+        };
+        let mut data = vec![0; 8 + StablecoinStateV1::INIT_SPACE];
+        data[..8].copy_from_slice(&solana_stablecoin::states::StablecoinState::DISCRIMINATOR);
+        legacy.serialize(&mut &mut data[8..]).unwrap();
+        self.set_account(&state, StoredAccount {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: solana_stablecoin::ID,
+            executable: false,
+        });
+        coin
+    }
+
+    pub fn create_stablecoin(&mut self, symbol: &str) -> Coin {
+        self.create_stablecoin_with(symbol, "USD", None, None, false)
+            .expect("create stablecoin")
+    }
+
+    pub fn create_stablecoin_with(
+        &mut self,
+        symbol: &str,
+        target_currency: &str,
+        fx_oracle: Option<Pubkey>,
+        token_2022_config: Option<Token2022Config>,
+        compliance_mode: bool,
+    ) -> std::result::Result<Coin, ProgramError> {
+        let creator = Pubkey::new_unique();
+        self.airdrop(&creator, 100 * LAMPORTS_PER_SOL);
+
+        let mint = Pubkey::new_unique();
+        let token_program = if token_2022_config.is_some() { spl_token_2022::ID } else { spl_token::ID };
+        let state = Self::pda(&[STABLECOIN_SEED, creator.as_ref(), symbol.as_bytes()]);
+        let factory: solana_stablecoin::states::FactoryState = self.state(&self.factory_state);
+        let coin = Coin {
+            state,
+            mint,
+            creator,
+            collateral_vault: get_associated_token_address_with_program_id(&state, &self.bond_mint, &spl_token::ID),
+            token_program,
+            fx_oracle,
+        };
+
+        let ix = Self::ix(
+            accounts::CreateStablecoin {
+                creator,
+                factory_state: self.factory_state,
+                stablecoin_state: state,
+                symbol_record: self.symbol_record(symbol),
+                stablecoin_index: Self::pda(&[
+                    STABLECOIN_INDEX_SEED,
+                    self.factory_state.as_ref(),
+                    factory.stablecoin_count.to_le_bytes().as_ref(),
+                ]),
+                creator_state: Self::user_state(&creator, &coin),
+                mint,
+                yield_mint: self.fee_mint,
+                collateral_vault: coin.collateral_vault,
+                bond_mint: self.bond_mint,
+                bond_info: self.bond_info,
+                fx_oracle,
+                fx_feed: fx_oracle.map(|_| self.fx_feed("USD", target_currency)),
+                metadata: mpl_token_metadata::accounts::Metadata::find_pda(&mint).0,
+                token_program: spl_token::ID,
+                stablecoin_token_program: token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_metadata_program: mpl_token_metadata::ID,
+                system_program: system_program::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            },
+            instruction::CreateStablecoin {
+                name: format!("{symbol} Dollar"),
+                symbol: symbol.to_string(),
+                target_currency: target_currency.to_string(),
+                uri: String::new(),
+                collateral_ratio: DEFAULT_COLLATERAL_RATIO,
+                fee_rate: 100,
+                token_2022_config,
+                compliance_mode,
+                stability_fee_bps: BASE_INTEREST_RATE,
+            },
+        );
+        self.process(&ix)?;
+        Ok(coin)
+    }
+}
+
+impl TestEnv {
+    // Admin instructions
+
+    pub fn shutdown_ix(&self, coin: &Coin) -> Instruction {
+        Self::ix(
+            accounts::ShutdownStablecoin {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+                stablecoin_mint: coin.mint,
+                stability_fee_vault: self.stability_fee_vault(coin),
+                oracle: self.bond_oracle,
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::ShutdownStablecoin {},
+        )
+    }
+
+    pub fn withdraw_stability_fees_ix(&self, coin: &Coin, recipient: &Pubkey, amount: u64) -> Instruction {
+        Self::ix(
+            accounts::WithdrawStabilityFees {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+                stablecoin_mint: coin.mint,
+                stability_fee_vault: self.stability_fee_vault(coin),
+                recipient: *recipient,
+                recipient_state: Self::user_state(recipient, coin),
+                recipient_stablecoin_account: Self::user_stablecoin_account(recipient, coin),
+                denylist_entry: Self::denylist_entry(coin, recipient),
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::WithdrawStabilityFees { amount },
+        )
+    }
+
+    pub fn reserve_symbol_ix(&self, symbol: &str) -> Instruction {
+        Self::ix(
+            accounts::ReserveSymbol {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                symbol_record: self.symbol_record(symbol),
+                system_program: system_program::ID,
+            },
+            instruction::ReserveSymbol { symbol: symbol.to_string() },
+        )
+    }
+
+    pub fn migrate_stablecoin_ix(&self, coin: &Coin, symbol: &str) -> Instruction {
+        Self::ix(
+            accounts::MigrateStablecoin {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+                symbol_record: self.symbol_record(symbol),
+                bond_mint: self.bond_mint,
+                yield_mint: self.fee_mint,
+                system_program: system_program::ID,
+            },
+            instruction::MigrateStablecoin {},
+        )
+    }
+
+    pub fn set_kyc_policy_ix(&self, coin: &Coin, policy: KycPolicy, requirements: KycRequirements) -> Instruction {
+        Self::ix(
+            accounts::SetKycPolicy {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+            },
+            instruction::SetKycPolicy { policy, requirements },
+        )
+    }
+
+    pub fn attest_kyc_ix(&self, user: &Pubkey, tier: u8, jurisdiction: &[u8; 2], expires_at: i64) -> Instruction {
+        Self::ix(
+            accounts::AttestKyc {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                kyc_attestation: self.kyc_attestation(user),
+                system_program: system_program::ID,
+            },
+            instruction::AttestKyc { user: *user, tier, jurisdiction: *jurisdiction, expires_at },
+        )
+    }
+
+    pub fn set_flow_limits_ix(&self, coin: &Coin, limits: FlowLimits) -> Instruction {
+        Self::ix(
+            accounts::SetFlowLimits {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+            },
+            instruction::SetFlowLimits { limits },
+        )
+    }
+
+    pub fn update_protocol_params_ix(&self, updates: ProtocolParamsUpdate) -> Instruction {
+        Self::ix(
+            accounts::UpdateProtocolParams {
+                admin: self.admin,
+                factory_state: self.factory_state,
+            },
+            instruction::UpdateProtocolParams { updates },
+        )
+    }
+
+    pub fn set_param_overrides_ix(&self, coin: &Coin, overrides: StablecoinParamOverrides) -> Instruction {
+        Self::ix(
+            accounts::SetStablecoinParamOverrides {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+            },
+            instruction::SetStablecoinParamOverrides { overrides },
+        )
+    }
+
+    pub fn set_stability_fee_ix(&self, coin: &Coin, rate_bps: u16) -> Instruction {
+        Self::ix(
+            accounts::SetStabilityFee {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+                stablecoin_mint: coin.mint,
+                stability_fee_vault: self.stability_fee_vault(coin),
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::SetStabilityFee { rate_bps },
+        )
+    }
+
+    pub fn release_symbol_ix(&self, symbol: &str) -> Instruction {
+        Self::ix(
+            accounts::ReleaseSymbol {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                symbol_record: self.symbol_record(symbol),
+            },
+            instruction::ReleaseSymbol {},
+        )
+    }
+
+    pub fn migrate_factory_ix(&self) -> Instruction {
+        Self::ix(
+            accounts::MigrateFactory {
+                admin: self.admin,
+                factory_state: self.factory_state,
+                system_program: system_program::ID,
+            },
+            instruction::MigrateFactory {},
+        )
+    }
+
+    /// Pauses through the guardian path when `signer` is the factory guardian,
+    /// otherwise through the coin's authority or pauser
+    pub fn pause_ix(&self, coin: &Coin, signer: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::PauseStablecoin {
+                admin: *signer,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+                system_program: system_program::ID,
+            },
+            instruction::PauseStablecoin {},
+        )
+    }
+
+    pub fn resume_ix(&self, coin: &Coin, signer: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::ResumeStablecoin {
+                admin: *signer,
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+                system_program: system_program::ID,
+            },
+            instruction::ResumeStablecoin {},
+        )
+    }
+
+    // Authority instructions
+
+    pub fn transfer_authority_ix(&self, coin: &Coin, authority: &Pubkey, new_authority: Option<Pubkey>) -> Instruction {
+        Self::ix(
+            accounts::TransferStablecoinAuthority {
+                authority: *authority,
+                stablecoin_state: coin.state,
+            },
+            instruction::TransferStablecoinAuthority { new_authority },
+        )
+    }
+
+    pub fn accept_authority_ix(&self, coin: &Coin, new_authority: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::AcceptStablecoinAuthority {
+                new_authority: *new_authority,
+                stablecoin_state: coin.state,
+            },
+            instruction::AcceptStablecoinAuthority {},
+        )
+    }
+
+    pub fn set_operators_ix(
+        &self,
+        coin: &Coin,
+        authority: &Pubkey,
+        metadata_editor: Option<Pubkey>,
+        pauser: Option<Pubkey>,
+        compliance_officer: Option<Pubkey>,
+    ) -> Instruction {
+        Self::ix(
+            accounts::SetStablecoinOperators {
+                authority: *authority,
+                stablecoin_state: coin.state,
+            },
+            instruction::SetStablecoinOperators { metadata_editor, pauser, compliance_officer },
+        )
+    }
+
+    pub fn update_interest_rate_ix(&self, coin: &Coin, rate: i16) -> Instruction {
+        Self::ix(
+            accounts::UpdateInterestRate {
+                authority: coin.creator,
+                stablecoin_state: coin.state,
+                stablecoin_mint: coin.mint,
+                stablecoin_token_program: coin.token_program,
+            },
+            instruction::UpdateInterestRate { rate },
+        )
+    }
+
+    // Compliance instructions, signed by the coin's creator
+
+    pub fn set_mint_policy_ix(&self, coin: &Coin, policy: MintPolicy) -> Instruction {
+        Self::ix(
+            accounts::SetMintPolicy {
+                authority: coin.creator,
+                stablecoin_state: coin.state,
+            },
+            instruction::SetMintPolicy { policy },
+        )
+    }
+
+    pub fn add_to_denylist_ix(&self, coin: &Coin, address: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::AddToDenylist {
+                officer: coin.creator,
+                stablecoin_state: coin.state,
+                denylist_entry: Self::denylist_entry(coin, address),
+                system_program: system_program::ID,
+            },
+            instruction::AddToDenylist { address: *address },
+        )
+    }
+
+    pub fn remove_from_denylist_ix(&self, coin: &Coin, address: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::RemoveFromDenylist {
+                officer: coin.creator,
+                stablecoin_state: coin.state,
+                denylist_entry: Self::denylist_entry(coin, address),
+            },
+            instruction::RemoveFromDenylist {},
+        )
+    }
+
+    pub fn add_minter_ix(&self, coin: &Coin, minter: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::AddMinter {
+                authority: coin.creator,
+                stablecoin_state: coin.state,
+                allowlist_entry: Self::allowlist_entry(coin, minter),
+                system_program: system_program::ID,
+            },
+            instruction::AddMinter { minter: *minter },
+        )
+    }
+
+    pub fn remove_minter_ix(&self, coin: &Coin, minter: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::RemoveMinter {
+                authority: coin.creator,
+                stablecoin_state: coin.state,
+                allowlist_entry: Self::allowlist_entry(coin, minter),
+            },
+            instruction::RemoveMinter {},
+        )
+    }
+
+    pub fn freeze_holder_ix(&self, coin: &Coin, holder_token_account: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::FreezeHolder {
+                officer: coin.creator,
+                stablecoin_state: coin.state,
+                stablecoin_mint: coin.mint,
+                holder_token_account: *holder_token_account,
+                stablecoin_token_program: coin.token_program,
+            },
+            instruction::FreezeHolder {},
+        )
+    }
+
+    pub fn thaw_holder_ix(&self, coin: &Coin, holder_token_account: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::ThawHolder {
+                officer: coin.creator,
+                stablecoin_state: coin.state,
+                stablecoin_mint: coin.mint,
+                holder_token_account: *holder_token_account,
+                stablecoin_token_program: coin.token_program,
+            },
+            instruction::ThawHolder {},
+        )
+    }
+
+    pub fn seize_holder_ix(
+        &self,
+        coin: &Coin,
+        officer: &Pubkey,
+        holder_token_account: &Pubkey,
+        destination_token_account: &Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        Self::ix(
+            accounts::SeizeHolder {
+                officer: *officer,
+                stablecoin_state: coin.state,
+                stablecoin_mint: coin.mint,
+                holder_token_account: *holder_token_account,
+                destination_token_account: *destination_token_account,
+                stablecoin_token_program: coin.token_program,
+            },
+            instruction::SeizeHolder { amount },
+        )
+    }
+
+    // Holder instructions
+
+    pub fn mint_ix(&self, coin: &Coin, user: &Pubkey, bond_account: &Pubkey, bond_amount: u64) -> Instruction {
+        Self::ix(
+            accounts::MintStablecoin {
+                user: *user,
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                user_state: Self::user_state(user, coin),
+                user_bond_account: *bond_account,
+                user_stablecoin_account: Self::user_stablecoin_account(user, coin),
+                stablecoin_mint: coin.mint,
+                collateral_vault: coin.collateral_vault,
+                sol_fee_vault: self.sol_fee_vault,
+                stability_fee_vault: self.stability_fee_vault(coin),
+                kyc_info: Self::kyc_pda(user),
+                kyc_attestation: self.existing_kyc_attestation(user),
+                denylist_entry: Self::denylist_entry(coin, user),
+                allowlist_entry: self.existing_allowlist_entry(coin, user),
+                oracle: self.bond_oracle,
+                fx_oracle: coin.fx_oracle,
+                token_program: spl_token::ID,
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            },
+            instruction::MintTokens { bond_amount, min_stablecoin_out: 0, max_fee: u64::MAX, expires_at: None },
+        )
+    }
+
+    pub fn burn_ix(&self, coin: &Coin, user: &Pubkey, bond_account: &Pubkey, stablecoin_amount: u64) -> Instruction {
+        Self::ix(
+            accounts::BurnStablecoin {
+                user: *user,
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                user_state: Self::user_state(user, coin),
+                user_bond_account: *bond_account,
+                user_stablecoin_account: Self::user_stablecoin_account(user, coin),
+                stablecoin_mint: coin.mint,
+                collateral_vault: coin.collateral_vault,
+                sol_fee_vault: self.sol_fee_vault,
+                stability_fee_vault: self.stability_fee_vault(coin),
+                kyc_info: Self::kyc_pda(user),
+                kyc_attestation: self.existing_kyc_attestation(user),
+                denylist_entry: Self::denylist_entry(coin, user),
+                oracle: self.bond_oracle,
+                fx_oracle: coin.fx_oracle,
+                token_program: spl_token::ID,
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            },
+            instruction::BurnTokens { stablecoin_amount, min_bonds_out: 0, max_fee: u64::MAX, expires_at: None },
+        )
+    }
+
+    pub fn repay_debt_ix(&self, coin: &Coin, payer: &Pubkey, owner: &Pubkey, amount: u64) -> Instruction {
+        Self::ix(
+            accounts::RepayDebt {
+                payer: *payer,
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                payer_state: Self::user_state(payer, coin),
+                payer_stablecoin_account: Self::user_stablecoin_account(payer, coin),
+                stablecoin_mint: coin.mint,
+                stability_fee_vault: self.stability_fee_vault(coin),
+                denylist_entry: Self::denylist_entry(coin, payer),
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::RepayDebt { owner: *owner, amount },
+        )
+    }
+
+    pub fn deposit_ix(&self, coin: &Coin, user: &Pubkey, bond_account: &Pubkey, bond_amount: u64) -> Instruction {
+        Self::ix(
+            accounts::DepositCollateral {
+                user: *user,
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                user_state: Self::user_state(user, coin),
+                user_bond_account: *bond_account,
+                collateral_vault: coin.collateral_vault,
+                token_program: spl_token::ID,
+            },
+            instruction::DepositCollateral { bond_amount },
+        )
+    }
+
+    pub fn withdraw_ix(&self, coin: &Coin, user: &Pubkey, bond_account: &Pubkey, bond_amount: u64) -> Instruction {
+        Self::ix(
+            accounts::WithdrawCollateral {
+                user: *user,
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                user_state: Self::user_state(user, coin),
+                user_bond_account: *bond_account,
+                collateral_vault: coin.collateral_vault,
+                stablecoin_mint: coin.mint,
+                stability_fee_vault: self.stability_fee_vault(coin),
+                denylist_entry: Self::denylist_entry(coin, user),
+                oracle: self.bond_oracle,
+                fx_oracle: coin.fx_oracle,
+                token_program: spl_token::ID,
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::WithdrawCollateral { bond_amount },
+        )
+    }
+
+    pub fn redeem_ix(
+        &self,
+        coin: &Coin,
+        user: &Pubkey,
+        bond_account: &Pubkey,
+        stablecoin_amount: u64,
+        mode: RedemptionMode,
+        min_bonds_out: u64,
+    ) -> Instruction {
+        Self::ix(
+            accounts::Redeem {
+                user: *user,
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                user_state: Self::user_state(user, coin),
+                user_bond_account: *bond_account,
+                user_stablecoin_account: self.holder_stablecoin_account(user, coin),
+                stablecoin_mint: coin.mint,
+                bond_mint: self.bond_mint,
+                collateral_vault: coin.collateral_vault,
+                bond_fee_vault: self.bond_fee_vault(),
+                stability_fee_vault: self.stability_fee_vault(coin),
+                kyc_info: Self::kyc_pda(user),
+                kyc_attestation: self.existing_kyc_attestation(user),
+                denylist_entry: Self::denylist_entry(coin, user),
+                oracle: self.bond_oracle,
+                fx_oracle: coin.fx_oracle,
+                token_program: spl_token::ID,
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::Redeem { stablecoin_amount, mode, min_bonds_out },
+        )
+    }
+
+    pub fn settle_ix(&self, coin: &Coin, user: &Pubkey, bond_account: &Pubkey, stablecoin_amount: u64) -> Instruction {
+        Self::ix(
+            accounts::SettleStablecoin {
+                user: *user,
+                stablecoin_state: coin.state,
+                user_state: Self::user_state(user, coin),
+                user_bond_account: *bond_account,
+                user_stablecoin_account: self.holder_stablecoin_account(user, coin),
+                stablecoin_mint: coin.mint,
+                collateral_vault: coin.collateral_vault,
+                denylist_entry: Self::denylist_entry(coin, user),
+                token_program: spl_token::ID,
+                stablecoin_token_program: coin.token_program,
+            },
+            instruction::SettleStablecoin { stablecoin_amount },
+        )
+    }
+
+    pub fn quote_mint_ix(&self, coin: &Coin, user: &Pubkey, bond_amount: u64) -> Instruction {
+        let user_state = Self::user_state(user, coin);
+        Self::ix(
+            accounts::QuoteMint {
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                user_state: self.exists(&user_state).then_some(user_state),
+                kyc_info: Self::kyc_pda(user),
+                kyc_attestation: self.existing_kyc_attestation(user),
+                denylist_entry: Self::denylist_entry(coin, user),
+                allowlist_entry: self.existing_allowlist_entry(coin, user),
+                oracle: self.bond_oracle,
+                fx_oracle: coin.fx_oracle,
+            },
+            instruction::QuoteMint { user: *user, bond_amount },
+        )
+    }
+
+    pub fn quote_burn_ix(&self, coin: &Coin, user: &Pubkey, stablecoin_amount: u64) -> Instruction {
+        Self::ix(
+            accounts::QuoteBurn {
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                user_state: Self::user_state(user, coin),
+                kyc_info: Self::kyc_pda(user),
+                kyc_attestation: self.existing_kyc_attestation(user),
+                denylist_entry: Self::denylist_entry(coin, user),
+                collateral_vault: coin.collateral_vault,
+                oracle: self.bond_oracle,
+                fx_oracle: coin.fx_oracle,
+            },
+            instruction::QuoteBurn { user: *user, stablecoin_amount },
+        )
+    }
+
+    pub fn quote_yield_ix(&self, coin: &Coin, user: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::QuoteYield {
+                factory_state: self.factory_state,
+                stablecoin_state: coin.state,
+                oracle: self.bond_oracle,
+                fx_oracle: coin.fx_oracle,
+                bond_info: self.bond_info,
+                bond_mint: self.bond_mint,
+            },
+            instruction::QuoteYield { user: *user },
+        )
+    }
+
+    pub fn quote_flow_capacity_ix(&self, coin: &Coin, user: &Pubkey) -> Instruction {
+        let user_state = Self::user_state(user, coin);
+        Self::ix(
+            accounts::QuoteFlowCapacity {
+                stablecoin_state: coin.state,
+                user_state: self.exists(&user_state).then_some(user_state),
+            },
+            instruction::QuoteFlowCapacity { user: *user },
+        )
+    }
+
+    pub fn accrue_stability_fee_ix(&self, coin: &Coin, payer: &Pubkey) -> Instruction {
+        Self::ix(
+            accounts::AccrueStabilityFee {
+                payer: *payer,
+                stablecoin_state: coin.state,
+                factory_state: self.factory_state,
+                stablecoin_mint: coin.mint,
+                stability_fee_vault: self.stability_fee_vault(coin),
+                stablecoin_token_program: coin.token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::AccrueStabilityFee {},
+        )
+    }
+
+    /// Mints against `bond_amount` of the holder's bonds with no slippage bounds
+    pub fn mint(&mut self, coin: &Coin, user: &Pubkey, bond_account: &Pubkey, bond_amount: u64) -> std::result::Result<(), ProgramError> {
+        let ix = self.mint_ix(coin, user, bond_account, bond_amount);
+        self.process(&ix)
+    }
+}
+
+/// The ProgramError a StablecoinError surfaces as
+pub fn program_error(error: StablecoinError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+/// Asserts that a transaction failed with `error`
+#[track_caller]
+pub fn assert_error(result: std::result::Result<(), ProgramError>, error: StablecoinError) {
+    assert_eq!(result.unwrap_err(), program_error(error));
+}
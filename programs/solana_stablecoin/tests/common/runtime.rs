@@ -0,0 +1,373 @@
+// tests/common/runtime.rs
+//
+// A minimal in-process runtime. Instructions are serialized into the same input
+// buffer the BPF loader builds, so account reallocation and ownership changes
+// behave as on chain, and cross-program invocations are routed through the
+// syscall stubs to the native SPL Token, Token-2022, Associated Token and System
+// program processors.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Once;
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{deserialize, ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+    system_program,
+};
+use anchor_spl::{
+    associated_token::spl_associated_token_account,
+    metadata::mpl_token_metadata,
+    token::spl_token,
+    token_2022::spl_token_2022,
+};
+
+/// Account as stored between transactions
+#[derive(Clone, Debug, Default)]
+pub struct StoredAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+#[derive(Default)]
+struct Context {
+    clock: Clock,
+    programs: Vec<Pubkey>,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+    logs: Vec<Vec<u8>>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::new(Context::default());
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        if std::env::var_os("PROGRAM_LOGS").is_some() {
+            println!("{message}");
+        }
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        CONTEXT.with(|ctx| ctx.borrow_mut().logs.push(fields.concat()));
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let caller = CONTEXT.with(|ctx| *ctx.borrow().programs.last().unwrap());
+        let pda_signers: Vec<Pubkey> = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<Result<_, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        let mut accounts = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let info = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            if meta.is_signer && !info.is_signer && !pda_signers.contains(info.key) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !info.is_writable {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let mut info = info.clone();
+            info.is_signer = meta.is_signer;
+            info.is_writable = meta.is_writable;
+            accounts.push(info);
+        }
+
+        execute(&instruction.program_id, &accounts, &instruction.data)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = CONTEXT.with(|ctx| ctx.borrow().clock.clone());
+        unsafe { std::ptr::write(var_addr as *mut Clock, clock) };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { std::ptr::write(var_addr as *mut Rent, Rent::default()) };
+        0
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        CONTEXT.with(|ctx| ctx.borrow().return_data.clone())
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        CONTEXT.with(|ctx| {
+            let mut ctx = ctx.borrow_mut();
+            let program = *ctx.programs.last().unwrap();
+            ctx.return_data = (!data.is_empty()).then(|| (program, data.to_vec()));
+        });
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        CONTEXT.with(|ctx| ctx.borrow().programs.len() as u64)
+    }
+}
+
+fn execute(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    CONTEXT.with(|ctx| ctx.borrow_mut().programs.push(*program_id));
+    let result = dispatch(program_id, accounts, data);
+    CONTEXT.with(|ctx| ctx.borrow_mut().programs.pop());
+    result
+}
+
+fn dispatch(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if *program_id == system_program::ID {
+        process_system(accounts, data)
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_token_2022::ID {
+        spl_token_2022::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_associated_token_account::ID {
+        spl_associated_token_account::processor::process_instruction(program_id, accounts, data)
+    } else if *program_id == mpl_token_metadata::ID {
+        // Metadata is not read back by the program, so its CPIs are accepted as is
+        Ok(())
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+fn process_system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let instruction: SystemInstruction =
+        bincode::deserialize(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    match instruction {
+        SystemInstruction::CreateAccount { lamports, space, owner } => {
+            let (from, to) = (&accounts[0], &accounts[1]);
+            if to.lamports() > 0 || !to.data_is_empty() || *to.owner != system_program::ID {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            transfer_lamports(from, to, lamports)?;
+            to.realloc(space as usize, true)?;
+            to.assign(&owner);
+            Ok(())
+        }
+        SystemInstruction::Transfer { lamports } => {
+            transfer_lamports(&accounts[0], &accounts[1], lamports)
+        }
+        SystemInstruction::Allocate { space } => {
+            let account = &accounts[0];
+            require_system_account(account)?;
+            if !account.data_is_empty() {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            account.realloc(space as usize, true)
+        }
+        SystemInstruction::Assign { owner } => {
+            let account = &accounts[0];
+            require_system_account(account)?;
+            account.assign(&owner);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// The system program can only debit, allocate or assign accounts it owns,
+/// with the owner's signature
+fn require_system_account(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *account.owner != system_program::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Ok(())
+}
+
+fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    require_system_account(from)?;
+    if !from.data_is_empty() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let remaining = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+/// Accounts keyed by address, plus the clock and the logs of the last transaction
+pub struct Runtime {
+    pub accounts: HashMap<Pubkey, StoredAccount>,
+    pub clock: Clock,
+    pub logs: Vec<Vec<u8>>,
+    pub return_data: Option<(Pubkey, Vec<u8>)>,
+}
+
+impl Runtime {
+    pub fn new() -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(Stubs));
+        });
+
+        let mut runtime = Runtime {
+            accounts: HashMap::new(),
+            clock: Clock {
+                slot: 1,
+                unix_timestamp: 1_700_000_000,
+                ..Clock::default()
+            },
+            logs: Vec::new(),
+            return_data: None,
+        };
+
+        for program in [
+            system_program::ID,
+            spl_token::ID,
+            spl_token_2022::ID,
+            spl_associated_token_account::ID,
+            mpl_token_metadata::ID,
+            solana_stablecoin::ID,
+        ] {
+            runtime.accounts.insert(program, StoredAccount {
+                lamports: 1,
+                owner: anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+                executable: true,
+                ..StoredAccount::default()
+            });
+        }
+
+        runtime.accounts.insert(anchor_lang::solana_program::sysvar::rent::ID, StoredAccount {
+            lamports: 1,
+            data: bincode::serialize(&Rent::default()).unwrap(),
+            owner: anchor_lang::solana_program::sysvar::ID,
+            executable: false,
+        });
+
+        runtime
+    }
+
+    /// Executes a single-instruction transaction. Account changes are kept only
+    /// if it succeeds.
+    pub fn process(&mut self, instruction: &Instruction) -> Result<(), ProgramError> {
+        let mut keys: Vec<(Pubkey, bool, bool)> = Vec::new();
+        for meta in &instruction.accounts {
+            match keys.iter_mut().find(|(key, _, _)| *key == meta.pubkey) {
+                Some(entry) => {
+                    entry.1 |= meta.is_signer;
+                    entry.2 |= meta.is_writable;
+                }
+                None => keys.push((meta.pubkey, meta.is_signer, meta.is_writable)),
+            }
+        }
+
+        let mut input = self.serialize(instruction, &keys);
+
+        CONTEXT.with(|ctx| {
+            let mut ctx = ctx.borrow_mut();
+            ctx.clock = self.clock.clone();
+            ctx.programs.clear();
+            ctx.return_data = None;
+            ctx.logs.clear();
+        });
+
+        let (program_id, account_infos, data) = unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+
+        let result = if *program_id == solana_stablecoin::ID {
+            CONTEXT.with(|ctx| ctx.borrow_mut().programs.push(*program_id));
+            let result = solana_stablecoin::entry(program_id, &account_infos, data)
+                .map_err(ProgramError::from);
+            CONTEXT.with(|ctx| ctx.borrow_mut().programs.pop());
+            result
+        } else {
+            execute(program_id, &account_infos, data)
+        };
+
+        self.logs = CONTEXT.with(|ctx| std::mem::take(&mut ctx.borrow_mut().logs));
+        self.return_data = CONTEXT.with(|ctx| ctx.borrow_mut().return_data.take());
+        result?;
+
+        for (key, _, _) in &keys {
+            let info = account_infos.iter().find(|info| info.key == key).unwrap();
+            if info.executable {
+                continue;
+            }
+            if info.lamports() == 0 {
+                self.accounts.remove(key);
+                continue;
+            }
+            self.accounts.insert(*key, StoredAccount {
+                lamports: info.lamports(),
+                data: info.data.borrow().to_vec(),
+                owner: *info.owner,
+                executable: false,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Lays out the instruction the way the BPF loader does, with room for each
+    /// account to grow by MAX_PERMITTED_DATA_INCREASE
+    fn serialize(&self, instruction: &Instruction, keys: &[(Pubkey, bool, bool)]) -> Vec<u128> {
+        let mut bytes: Vec<u8> = Vec::new();
+        bytes.extend_from_slice(&(instruction.accounts.len() as u64).to_le_bytes());
+
+        for (position, meta) in instruction.accounts.iter().enumerate() {
+            let first = instruction.accounts.iter().position(|m| m.pubkey == meta.pubkey).unwrap();
+            if first < position {
+                bytes.push(first as u8);
+                bytes.extend_from_slice(&[0; 7]);
+                continue;
+            }
+
+            let (_, is_signer, is_writable) = keys.iter().find(|(key, _, _)| *key == meta.pubkey).unwrap();
+            let account = self.accounts.get(&meta.pubkey).cloned().unwrap_or_else(|| StoredAccount {
+                owner: system_program::ID,
+                ..StoredAccount::default()
+            });
+
+            bytes.push(u8::MAX);
+            bytes.push(*is_signer as u8);
+            bytes.push(*is_writable as u8);
+            bytes.push(account.executable as u8);
+            bytes.extend_from_slice(&[0; 4]);
+            bytes.extend_from_slice(meta.pubkey.as_ref());
+            bytes.extend_from_slice(account.owner.as_ref());
+            bytes.extend_from_slice(&account.lamports.to_le_bytes());
+            bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&account.data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            bytes.resize(bytes.len().next_multiple_of(8), 0);
+            bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+        }
+
+        bytes.extend_from_slice(&(instruction.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&instruction.data);
+        bytes.extend_from_slice(instruction.program_id.as_ref());
+
+        // u128 words keep the buffer aligned like the loader's
+        let mut input = vec![0u128; bytes.len().div_ceil(16)];
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), input.as_mut_ptr() as *mut u8, bytes.len());
+        }
+        input
+    }
+}
@@ -0,0 +1,181 @@
+// tests/compliance.rs
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use solana_stablecoin::{
+    errors::StablecoinError,
+    events::HolderSeized,
+    states::{
+        stablecoin::{MintPolicy, RedemptionMode},
+        token_config::Token2022Config,
+    },
+};
+
+/// A compliance-mode coin with two holders at 150%, the first about to be
+/// denylisted
+fn compliance_coin(env: &mut TestEnv) -> (Coin, [(Pubkey, Pubkey); 2]) {
+    let coin = env
+        .create_stablecoin_with("USDC", "USD", None, None, true)
+        .expect("create stablecoin");
+    let alice = env.new_user(1_000 * ONE);
+    let bob = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice.0, &alice.1, 300 * ONE).expect("mint");
+    env.mint(&coin, &bob.0, &bob.1, 300 * ONE).expect("mint");
+    (coin, [alice, bob])
+}
+
+#[test]
+fn denylisted_holders_cannot_exit() {
+    let mut env = TestEnv::new();
+    let (coin, [(alice, alice_bonds), _]) = compliance_coin(&mut env);
+    let ix = env.deposit_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    env.process(&ix).expect("deposit");
+
+    let ix = env.add_to_denylist_ix(&coin, &alice);
+    env.process(&ix).expect("denylist");
+
+    let ix = env.withdraw_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    assert_error(env.process(&ix), StablecoinError::AddressDenylisted);
+
+    let ix = env.burn_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    assert_error(env.process(&ix), StablecoinError::AddressDenylisted);
+
+    let ix = env.redeem_ix(&coin, &alice, &alice_bonds, 100 * ONE, RedemptionMode::LowestHealth, 0);
+    assert_error(env.process(&ix), StablecoinError::AddressDenylisted);
+
+    let ix = env.shutdown_ix(&coin);
+    env.process(&ix).expect("shutdown");
+    let ix = env.settle_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    assert_error(env.process(&ix), StablecoinError::AddressDenylisted);
+}
+
+#[test]
+fn denylisted_holders_without_a_position_cannot_redeem() {
+    let mut env = TestEnv::new();
+    let (coin, [(alice, _), _]) = compliance_coin(&mut env);
+
+    let (carol, carol_bonds) = env.new_user(0);
+    env.move_stablecoins(&coin, &alice, &carol, 100 * ONE);
+    let ix = env.add_to_denylist_ix(&coin, &carol);
+    env.process(&ix).expect("denylist");
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 100 * ONE, RedemptionMode::ProRata, 0);
+    assert_error(env.process(&ix), StablecoinError::AddressDenylisted);
+}
+
+#[test]
+fn other_holders_still_exit() {
+    let mut env = TestEnv::new();
+    let (coin, [(alice, _), (bob, bob_bonds)]) = compliance_coin(&mut env);
+
+    let ix = env.add_to_denylist_ix(&coin, &alice);
+    env.process(&ix).expect("denylist");
+
+    let ix = env.burn_ix(&coin, &bob, &bob_bonds, 100 * ONE);
+    env.process(&ix).expect("burn");
+}
+
+#[test]
+fn removing_the_denylist_entry_restores_exits() {
+    let mut env = TestEnv::new();
+    let (coin, [(alice, alice_bonds), _]) = compliance_coin(&mut env);
+
+    let ix = env.add_to_denylist_ix(&coin, &alice);
+    env.process(&ix).expect("denylist");
+    let ix = env.burn_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    assert_error(env.process(&ix), StablecoinError::AddressDenylisted);
+
+    let ix = env.remove_from_denylist_ix(&coin, &alice);
+    env.process(&ix).expect("remove from denylist");
+    assert!(!env.exists(&TestEnv::denylist_entry(&coin, &alice)));
+
+    let ix = env.burn_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    env.process(&ix).expect("burn");
+}
+
+#[test]
+fn thawed_holders_can_exit() {
+    let mut env = TestEnv::new();
+    let (coin, [(alice, alice_bonds), _]) = compliance_coin(&mut env);
+    let alice_account = TestEnv::user_stablecoin_account(&alice, &coin);
+
+    let ix = env.freeze_holder_ix(&coin, &alice_account);
+    env.process(&ix).expect("freeze");
+    let ix = env.burn_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    assert!(env.process(&ix).is_err());
+
+    let ix = env.thaw_holder_ix(&coin, &alice_account);
+    env.process(&ix).expect("thaw");
+    assert!(!env.is_frozen(&alice_account));
+
+    let ix = env.burn_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    env.process(&ix).expect("burn");
+}
+
+#[test]
+fn only_allowlisted_minters_mint_under_the_allowlist_policy() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+
+    let ix = env.set_mint_policy_ix(&coin, MintPolicy::AllowlistOnly);
+    env.process(&ix).expect("set mint policy");
+    assert_error(env.mint(&coin, &alice, &alice_bonds, 300 * ONE), StablecoinError::MinterNotAllowlisted);
+
+    let ix = env.add_minter_ix(&coin, &alice);
+    env.process(&ix).expect("add minter");
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let ix = env.remove_minter_ix(&coin, &alice);
+    env.process(&ix).expect("remove minter");
+    assert!(!env.exists(&TestEnv::allowlist_entry(&coin, &alice)));
+    assert_error(env.mint(&coin, &alice, &alice_bonds, 300 * ONE), StablecoinError::MinterNotAllowlisted);
+}
+
+#[test]
+fn seize_moves_a_frozen_balance_with_the_permanent_delegate() {
+    let mut env = TestEnv::new();
+    let config = Token2022Config { permanent_delegate: true, ..Token2022Config::default() };
+    let coin = env
+        .create_stablecoin_with("USDC", "USD", None, Some(config), true)
+        .expect("create stablecoin");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let (treasury, _) = env.new_user(0);
+    env.move_stablecoins(&coin, &alice, &treasury, 0);
+    let alice_account = TestEnv::user_stablecoin_account(&alice, &coin);
+    let treasury_account = TestEnv::wallet_stablecoin_account(&treasury, &coin);
+    let balance = env.token_balance(&alice_account);
+
+    let ix = env.freeze_holder_ix(&coin, &alice_account);
+    env.process(&ix).expect("freeze");
+
+    let ix = env.seize_holder_ix(&coin, &coin.creator, &alice_account, &treasury_account, 50 * ONE);
+    env.process(&ix).expect("seize");
+
+    assert_eq!(env.token_balance(&alice_account), balance - 50 * ONE);
+    assert_eq!(env.token_balance(&treasury_account), 50 * ONE);
+    assert!(env.is_frozen(&alice_account));
+
+    let event = &env.events::<HolderSeized>()[0];
+    assert_eq!(event.holder, TestEnv::user_state(&alice, &coin));
+    assert_eq!(event.destination, treasury_account);
+    assert_eq!(event.amount, 50 * ONE);
+}
+
+#[test]
+fn seize_requires_the_officer_and_the_permanent_delegate() {
+    let mut env = TestEnv::new();
+    let (coin, [(alice, _), (bob, _)]) = compliance_coin(&mut env);
+    let alice_account = TestEnv::user_stablecoin_account(&alice, &coin);
+    let bob_account = TestEnv::user_stablecoin_account(&bob, &coin);
+
+    let ix = env.seize_holder_ix(&coin, &bob, &alice_account, &bob_account, ONE);
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+
+    // Classic SPL mints have no permanent delegate
+    let ix = env.seize_holder_ix(&coin, &coin.creator, &alice_account, &bob_account, ONE);
+    assert_error(env.process(&ix), StablecoinError::PermanentDelegateDisabled);
+}
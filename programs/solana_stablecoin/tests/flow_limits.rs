@@ -0,0 +1,69 @@
+// tests/flow_limits.rs
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use solana_stablecoin::{
+    errors::StablecoinError,
+    states::{flow_limits::FlowLimits, stablecoin::RedemptionMode},
+};
+
+/// A holder with a 200 debt position on 400 bonds, capped at a net 50
+/// stablecoins out per hour
+fn capped_position(env: &mut TestEnv) -> (Coin, Pubkey, Pubkey) {
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+    let ix = env.deposit_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    env.process(&ix).expect("deposit");
+
+    let limits = FlowLimits {
+        window_duration: 3_600,
+        user_max_net_redeem: 50 * ONE,
+        ..FlowLimits::default()
+    };
+    let ix = env.set_flow_limits_ix(&coin, limits);
+    env.process(&ix).expect("set flow limits");
+    (coin, alice, alice_bonds)
+}
+
+#[test]
+fn withdrawals_count_against_the_redemption_cap() {
+    let mut env = TestEnv::new();
+    let (coin, alice, alice_bonds) = capped_position(&mut env);
+
+    let ix = env.withdraw_ix(&coin, &alice, &alice_bonds, 60 * ONE);
+    assert_error(env.process(&ix), StablecoinError::UserFlowLimitExceeded);
+
+    let ix = env.withdraw_ix(&coin, &alice, &alice_bonds, 50 * ONE);
+    env.process(&ix).expect("withdraw");
+
+    // The cap resets with the window
+    let ix = env.withdraw_ix(&coin, &alice, &alice_bonds, 10 * ONE);
+    assert_error(env.process(&ix), StablecoinError::UserFlowLimitExceeded);
+    env.warp(3_600);
+    env.refresh_oracle(&env.bond_oracle.clone());
+    env.process(&ix).expect("withdraw");
+}
+
+#[test]
+fn redemptions_count_against_the_redemption_cap() {
+    let mut env = TestEnv::new();
+    let (coin, alice, _) = capped_position(&mut env);
+    let (carol, carol_bonds) = env.new_user(0);
+    env.move_stablecoins(&coin, &alice, &carol, 150 * ONE);
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 60 * ONE, RedemptionMode::LowestHealth, 0);
+    assert_error(env.process(&ix), StablecoinError::UserFlowLimitExceeded);
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 60 * ONE, RedemptionMode::ProRata, 0);
+    assert_error(env.process(&ix), StablecoinError::UserFlowLimitExceeded);
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 30 * ONE, RedemptionMode::LowestHealth, 0);
+    env.process(&ix).expect("redeem");
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 20 * ONE, RedemptionMode::ProRata, 0);
+    env.process(&ix).expect("redeem");
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 1, RedemptionMode::ProRata, 0);
+    assert_error(env.process(&ix), StablecoinError::UserFlowLimitExceeded);
+}
@@ -0,0 +1,138 @@
+// tests/governance.rs
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use solana_stablecoin::{
+    constants::ORACLE_STALENESS_THRESHOLD,
+    errors::StablecoinError,
+    states::{FactoryState, ProtocolParamsUpdate, StablecoinParamOverrides, StablecoinState},
+};
+
+/// A protocol params update that leaves every parameter unchanged
+fn no_updates() -> ProtocolParamsUpdate {
+    ProtocolParamsUpdate {
+        oracle_staleness_threshold: None,
+        rate_freshness_threshold: None,
+        rate_update_interval: None,
+        rebase_interval: None,
+        protocol_fee_bps: None,
+        redemption_fee_bps: None,
+        max_allowed_bonds: None,
+        max_allowed_collectors: None,
+    }
+}
+
+#[test]
+fn authority_transfers_complete_on_acceptance() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let bob = Pubkey::new_unique();
+
+    let ix = env.accept_authority_ix(&coin, &bob);
+    assert_error(env.process(&ix), StablecoinError::NoPendingAuthority);
+
+    let ix = env.transfer_authority_ix(&coin, &coin.creator, Some(bob));
+    env.process(&ix).expect("transfer authority");
+
+    // Only the proposed key can accept, and nothing changes until it does
+    let ix = env.accept_authority_ix(&coin, &Pubkey::new_unique());
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.authority, coin.creator);
+
+    let ix = env.accept_authority_ix(&coin, &bob);
+    env.process(&ix).expect("accept authority");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.authority, bob);
+    assert_eq!(state.pending_authority, None);
+
+    let ix = env.transfer_authority_ix(&coin, &coin.creator, Some(coin.creator));
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+}
+
+#[test]
+fn pausers_hold_a_pause_separate_from_the_guardian() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    let pauser = Pubkey::new_unique();
+    env.airdrop(&pauser, LAMPORTS_PER_SOL);
+
+    let ix = env.pause_ix(&coin, &pauser);
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+    let ix = env.set_operators_ix(&coin, &pauser, None, Some(pauser), None);
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+
+    let ix = env.set_operators_ix(&coin, &coin.creator, None, Some(pauser), None);
+    env.process(&ix).expect("set operators");
+
+    let ix = env.pause_ix(&coin, &pauser);
+    env.process(&ix).expect("pause");
+    assert_error(env.process(&ix), StablecoinError::AlreadyPaused);
+    assert_error(env.mint(&coin, &alice, &alice_bonds, 300 * ONE), StablecoinError::StablecoinPaused);
+
+    // The guardian's pause is its own; lifting it leaves the pauser's in place
+    let ix = env.pause_ix(&coin, &env.admin.clone());
+    env.process(&ix).expect("guardian pause");
+    let ix = env.resume_ix(&coin, &env.admin.clone());
+    env.process(&ix).expect("guardian resume");
+    let state: StablecoinState = env.state(&coin.state);
+    assert!(state.paused_by_authority);
+    assert!(!state.paused_by_factory);
+
+    let ix = env.resume_ix(&coin, &pauser);
+    env.process(&ix).expect("resume");
+    assert_error(env.process(&ix), StablecoinError::NotPaused);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+}
+
+#[test]
+fn protocol_params_update_within_bounds() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+
+    let ix = env.update_protocol_params_ix(ProtocolParamsUpdate {
+        oracle_staleness_threshold: Some(1),
+        ..no_updates()
+    });
+    assert_error(env.process(&ix), StablecoinError::InvalidOracleStaleness);
+
+    let mut ix = env.update_protocol_params_ix(ProtocolParamsUpdate {
+        oracle_staleness_threshold: Some(1_800),
+        ..no_updates()
+    });
+    ix.accounts[0].pubkey = alice;
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+
+    ix.accounts[0].pubkey = env.admin;
+    env.process(&ix).expect("update protocol params");
+
+    let factory: FactoryState = env.state(&env.factory_state);
+    assert_eq!(factory.protocol_params.oracle_staleness_threshold, 1_800);
+
+    // A price older than the default threshold is now accepted
+    env.warp(ORACLE_STALENESS_THRESHOLD + 1);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+}
+
+#[test]
+fn param_overrides_apply_to_one_coin() {
+    let mut env = TestEnv::new();
+    let wide = env.create_stablecoin("USDW");
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+
+    let overrides = StablecoinParamOverrides {
+        oracle_staleness_threshold: Some(1_800),
+        ..StablecoinParamOverrides::default()
+    };
+    let ix = env.set_param_overrides_ix(&wide, overrides);
+    env.process(&ix).expect("set param overrides");
+
+    env.warp(ORACLE_STALENESS_THRESHOLD + 1);
+    assert_error(env.mint(&coin, &alice, &alice_bonds, 300 * ONE), StablecoinError::StaleOraclePrice);
+    env.mint(&wide, &alice, &alice_bonds, 300 * ONE).expect("mint");
+}
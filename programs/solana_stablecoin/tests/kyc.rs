@@ -0,0 +1,83 @@
+// tests/kyc.rs
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use solana_stablecoin::{
+    errors::StablecoinError,
+    states::{stablecoin::{KycPolicy, KycRequirements}, KycAttestation},
+};
+
+/// Tier 2 or above, attested in the US or Mexico
+fn tiered_requirements() -> KycRequirements {
+    let mut jurisdictions = [[0; 2]; 8];
+    jurisdictions[0] = *b"US";
+    jurisdictions[1] = *b"MX";
+    KycRequirements { min_tier: 2, jurisdictions }
+}
+
+#[test]
+fn tiered_policy_checks_the_attestation() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let ix = env.set_kyc_policy_ix(&coin, KycPolicy::Required, tiered_requirements());
+    env.process(&ix).expect("set kyc policy");
+
+    // Etherfuse approval alone is no longer enough
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    assert_error(env.mint(&coin, &alice, &alice_bonds, 300 * ONE), StablecoinError::MissingKycAttestation);
+
+    let expires_at = env.now() + 1_000;
+    let ix = env.attest_kyc_ix(&alice, 1, b"US", expires_at);
+    env.process(&ix).expect("attest");
+    assert_error(env.mint(&coin, &alice, &alice_bonds, 300 * ONE), StablecoinError::KycTierTooLow);
+
+    let ix = env.attest_kyc_ix(&alice, 2, b"FR", expires_at);
+    env.process(&ix).expect("attest");
+    assert_error(env.mint(&coin, &alice, &alice_bonds, 300 * ONE), StablecoinError::JurisdictionNotAllowed);
+
+    let ix = env.attest_kyc_ix(&alice, 2, b"MX", expires_at);
+    env.process(&ix).expect("attest");
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let attestation: KycAttestation = env.state(&env.kyc_attestation(&alice));
+    assert_eq!((attestation.user, attestation.tier, attestation.jurisdiction), (alice, 2, *b"MX"));
+
+    // Lapsed attestations block burns too
+    env.warp(1_000);
+    env.refresh_oracle(&env.bond_oracle.clone());
+    let ix = env.burn_ix(&coin, &alice, &alice_bonds, 10 * ONE);
+    assert_error(env.process(&ix), StablecoinError::KycAttestationExpired);
+}
+
+#[test]
+fn requirements_are_validated() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+
+    let ix = env.set_kyc_policy_ix(&coin, KycPolicy::NotRequired, tiered_requirements());
+    assert_error(env.process(&ix), StablecoinError::InvalidKycRequirements);
+
+    let mut requirements = tiered_requirements();
+    requirements.jurisdictions[2] = *b"fr";
+    let ix = env.set_kyc_policy_ix(&coin, KycPolicy::Required, requirements);
+    assert_error(env.process(&ix), StablecoinError::InvalidJurisdiction);
+
+    let ix = env.attest_kyc_ix(&Pubkey::new_unique(), 2, b"u1", env.now() + 1_000);
+    assert_error(env.process(&ix), StablecoinError::InvalidJurisdiction);
+
+    // Waiving KYC clears any requirements
+    let ix = env.set_kyc_policy_ix(&coin, KycPolicy::NotRequired, KycRequirements::default());
+    env.process(&ix).expect("set kyc policy");
+}
+
+#[test]
+fn only_the_admin_attests() {
+    let mut env = TestEnv::new();
+    let intruder = Pubkey::new_unique();
+    env.airdrop(&intruder, LAMPORTS_PER_SOL);
+
+    let mut ix = env.attest_kyc_ix(&intruder, 5, b"US", env.now() + 1_000);
+    ix.accounts[0].pubkey = intruder;
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+}
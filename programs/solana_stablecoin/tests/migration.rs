@@ -0,0 +1,167 @@
+// tests/migration.rs
+mod common;
+
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token::spl_token;
+use common::*;
+use solana_stablecoin::{
+    constants::ORACLE_STALENESS_THRESHOLD,
+    errors::StablecoinError,
+    events::AccountMigrated,
+    states::{legacy::FactoryStateV1, FactoryState, StablecoinState, SymbolRecord},
+};
+
+#[test]
+fn migration_upgrades_a_v1_coin_and_registers_its_symbol() {
+    let mut env = TestEnv::with_factory_id(0);
+    // A 9-decimal bond, so the migrated bond scale can't pass as the stablecoin's
+    env.create_mint(&env.bond_mint.clone(), &Pubkey::new_unique(), 9, &spl_token::ID);
+    let coin = env.create_v1_stablecoin("USDV");
+
+    let ix = env.migrate_stablecoin_ix(&coin, "USDV");
+    env.process(&ix).expect("migrate");
+    assert_eq!(env.events::<AccountMigrated>()[0].from_version, 1);
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert!(state.is_current());
+    assert_eq!(state.factory, env.factory_state);
+    assert_eq!(state.symbol, "USDV");
+    assert_eq!(state.bond_scale, 1_000_000_000);
+    assert_eq!(state.yield_scale, 1_000_000);
+
+    let record: SymbolRecord = env.state(&env.symbol_record("USDV"));
+    assert_eq!(record.stablecoin, coin.state);
+    assert!(!record.is_reserved);
+
+    // The registry now blocks new coins from the symbol
+    assert!(env.create_stablecoin_with("USDV", "USD", None, None, false).is_err());
+
+    let ix = env.migrate_stablecoin_ix(&coin, "USDV");
+    assert_error(env.process(&ix), StablecoinError::AlreadyMigrated);
+}
+
+#[test]
+fn migration_rejects_a_symbol_held_by_another_coin() {
+    let mut env = TestEnv::with_factory_id(0);
+    env.create_stablecoin("USDV");
+    let coin = env.create_v1_stablecoin("USDV");
+
+    let ix = env.migrate_stablecoin_ix(&coin, "USDV");
+    assert_error(env.process(&ix), StablecoinError::SymbolTaken);
+}
+
+#[test]
+fn migration_normalizes_legacy_symbols() {
+    let mut env = TestEnv::with_factory_id(0);
+    let coin = env.create_v1_stablecoin("usd-v");
+
+    let ix = env.migrate_stablecoin_ix(&coin, "USDV");
+    env.process(&ix).expect("migrate");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.symbol, "USDV");
+    assert_eq!(state.original_symbol, "usd-v");
+    let record: SymbolRecord = env.state(&env.symbol_record("USDV"));
+    assert_eq!(record.stablecoin, coin.state);
+
+    // Nothing is left of a symbol without letters or digits
+    let coin = env.create_v1_stablecoin("$$");
+    let ix = env.migrate_stablecoin_ix(&coin, "");
+    assert_error(env.process(&ix), StablecoinError::InvalidSymbol);
+}
+
+#[test]
+fn migration_claims_reserved_and_prefunded_symbol_records() {
+    let mut env = TestEnv::with_factory_id(0);
+
+    let reserved = env.create_v1_stablecoin("USDR");
+    let ix = env.reserve_symbol_ix("USDR");
+    env.process(&ix).expect("reserve symbol");
+    let ix = env.migrate_stablecoin_ix(&reserved, "USDR");
+    env.process(&ix).expect("migrate");
+    let record: SymbolRecord = env.state(&env.symbol_record("USDR"));
+    assert_eq!(record.stablecoin, reserved.state);
+    assert!(!record.is_reserved);
+
+    // Lamports sent to the record address ahead of time can't block the claim
+    let prefunded = env.create_v1_stablecoin("USDP");
+    env.airdrop(&env.symbol_record("USDP"), 1);
+    let ix = env.migrate_stablecoin_ix(&prefunded, "USDP");
+    env.process(&ix).expect("migrate");
+    let record: SymbolRecord = env.state(&env.symbol_record("USDP"));
+    assert_eq!(record.stablecoin, prefunded.state);
+}
+
+/// Rewrites the singleton factory in the version 1 layout, as deployed before
+/// schema versioning and protocol params
+fn downgrade_factory(env: &mut TestEnv) {
+    let factory: FactoryState = env.state(&env.factory_state);
+    let legacy = FactoryStateV1 {
+        admin: factory.admin,
+        fee_vault: factory.fee_vault,
+        is_paused: factory.is_paused,
+        min_collateral_ratio: factory.min_collateral_ratio,
+        base_fee_rate: factory.base_fee_rate,
+        stablecoin_count: factory.stablecoin_count,
+        last_update: factory.last_update,
+        allowed_bond_configs: factory.allowed_bond_configs,
+        bond_collateral_tracking: factory.bond_collateral_tracking,
+        authorized_collectors: factory.authorized_collectors,
+        protocol_version: 1,
+        bump: factory.bump,
+        reserved: factory.reserved,
+    };
+    let mut data = vec![0; 8 + FactoryStateV1::INIT_SPACE];
+    data[..8].copy_from_slice(&FactoryState::DISCRIMINATOR);
+    legacy.serialize(&mut &mut data[8..]).unwrap();
+    let factory_state = env.factory_state;
+    env.set_account(&factory_state, StoredAccount {
+        lamports: LAMPORTS_PER_SOL / 100,
+        data,
+        owner: solana_stablecoin::ID,
+        executable: false,
+    });
+}
+
+#[test]
+fn factory_migration_upgrades_a_v1_factory() {
+    let mut env = TestEnv::with_factory_id(0);
+    downgrade_factory(&mut env);
+    // The old layout doesn't decode as the current FactoryState
+    assert!(env.process(&env.reserve_symbol_ix("USDR")).is_err());
+
+    let mut ix = env.migrate_factory_ix();
+    ix.accounts[0].pubkey = Pubkey::new_unique();
+    env.airdrop(&ix.accounts[0].pubkey, LAMPORTS_PER_SOL);
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+
+    let ix = env.migrate_factory_ix();
+    env.process(&ix).expect("migrate factory");
+    assert_eq!(env.events::<AccountMigrated>()[0].from_version, 1);
+
+    let factory: FactoryState = env.state(&env.factory_state);
+    assert!(factory.is_current());
+    assert_eq!(factory.admin, env.admin);
+    assert_eq!(factory.allowed_bond_configs.len(), 1);
+    assert_eq!(factory.protocol_params.oracle_staleness_threshold, ORACLE_STALENESS_THRESHOLD);
+
+    assert_error(env.process(&ix), StablecoinError::AlreadyMigrated);
+    env.process(&env.reserve_symbol_ix("USDR")).expect("reserve symbol");
+}
+
+#[test]
+fn only_reserved_symbols_can_be_released() {
+    let mut env = TestEnv::new();
+    let ix = env.reserve_symbol_ix("USDR");
+    env.process(&ix).expect("reserve symbol");
+    assert!(env.create_stablecoin_with("USDR", "USD", None, None, false).is_err());
+
+    let ix = env.release_symbol_ix("USDR");
+    env.process(&ix).expect("release symbol");
+    assert!(!env.exists(&env.symbol_record("USDR")));
+    env.create_stablecoin_with("USDR", "USD", None, None, false).expect("create stablecoin");
+
+    // The record is now claimed by a live coin
+    let ix = env.release_symbol_ix("USDR");
+    assert_error(env.process(&ix), StablecoinError::SymbolNotReserved);
+}
@@ -0,0 +1,100 @@
+// tests/oracle.rs
+mod common;
+
+use common::*;
+use solana_stablecoin::{constants::ORACLE_STALENESS_THRESHOLD, errors::StablecoinError, events::{FxFeedSet, StablecoinMinted}};
+
+#[test]
+fn mint_requires_a_fresh_bond_feed() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (user, bonds) = env.new_user(1_000 * ONE);
+
+    env.mint(&coin, &user, &bonds, 100 * ONE).expect("mint at a fresh price");
+
+    // The feed's round is now older than the staleness threshold
+    env.warp(ORACLE_STALENESS_THRESHOLD + 1);
+    assert_error(env.mint(&coin, &user, &bonds, 100 * ONE), StablecoinError::StaleOraclePrice);
+
+    let oracle = env.bond_oracle;
+    env.refresh_oracle(&oracle);
+    env.mint(&coin, &user, &bonds, 100 * ONE).expect("mint after the feed updates");
+}
+
+#[test]
+fn bond_price_honors_the_feed_scale() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (user, bonds) = env.new_user(1_000 * ONE);
+
+    // 1.05 with nine decimals
+    let oracle = env.bond_oracle;
+    env.set_oracle(&oracle, 1_050_000_000, 9);
+    env.mint(&coin, &user, &bonds, 300 * ONE).expect("mint");
+
+    // 300 bonds at 1.05, collateralized at 150%
+    let minted = env.events::<StablecoinMinted>()[0].mint_amount;
+    assert_eq!(minted, 210 * ONE);
+}
+
+#[test]
+fn negative_bond_price_is_rejected() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (user, bonds) = env.new_user(1_000 * ONE);
+
+    let oracle = env.bond_oracle;
+    env.set_oracle(&oracle, -1_000_000, 6);
+    assert_error(env.mint(&coin, &user, &bonds, 300 * ONE), StablecoinError::InvalidOraclePrice);
+}
+
+#[test]
+fn fx_priced_stablecoin_uses_the_registered_feed() {
+    let mut env = TestEnv::new();
+    let fx_oracle = anchor_lang::prelude::Pubkey::new_unique();
+    env.set_oracle(&fx_oracle, 17_000_000, 6);
+    let ix = env.set_fx_feed_ix("USD", "MXN", &fx_oracle);
+    env.process(&ix).expect("register feed");
+    assert_eq!(env.events::<FxFeedSet>()[0].new_aggregator, fx_oracle);
+
+    let coin = env
+        .create_stablecoin_with("MXNX", "MXN", Some(fx_oracle), None, false)
+        .expect("create stablecoin");
+
+    let (user, bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &user, &bonds, 300 * ONE).expect("mint");
+
+    // 300 bonds at 1.00 USD and 17 MXN per USD, collateralized at 150%
+    assert_eq!(env.events::<StablecoinMinted>()[0].mint_amount, 3_400 * ONE);
+}
+
+#[test]
+fn stablecoin_rejects_an_unregistered_fx_feed() {
+    let mut env = TestEnv::new();
+    let registered = anchor_lang::prelude::Pubkey::new_unique();
+    let rogue = anchor_lang::prelude::Pubkey::new_unique();
+    env.set_oracle(&registered, 17_000_000, 6);
+    env.set_oracle(&rogue, 1_000_000, 6);
+
+    // No feed registered for the pair yet
+    assert!(env.create_stablecoin_with("MXNX", "MXN", Some(rogue), None, false).is_err());
+
+    let ix = env.set_fx_feed_ix("USD", "MXN", &registered);
+    env.process(&ix).expect("register feed");
+
+    let result = env.create_stablecoin_with("MXNX", "MXN", Some(rogue), None, false);
+    assert_error(result.map(|_| ()), StablecoinError::InvalidFxFeed);
+}
+
+#[test]
+fn only_the_admin_sets_fx_feeds() {
+    let mut env = TestEnv::new();
+    let fx_oracle = anchor_lang::prelude::Pubkey::new_unique();
+    env.set_oracle(&fx_oracle, 17_000_000, 6);
+
+    let mut ix = env.set_fx_feed_ix("USD", "MXN", &fx_oracle);
+    let impostor = anchor_lang::prelude::Pubkey::new_unique();
+    env.airdrop(&impostor, LAMPORTS_PER_SOL);
+    ix.accounts[0].pubkey = impostor;
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+}
@@ -0,0 +1,175 @@
+// tests/quotes.rs
+mod common;
+
+use common::*;
+use solana_stablecoin::{
+    constants::{REBASE_INTERVAL, YEAR_IN_SECONDS},
+    errors::StablecoinError,
+    states::{
+        flow_limits::FlowLimits,
+        quote::{BurnQuote, FlowCapacity, MintQuote, YieldQuote},
+        stablecoin::MintPolicy,
+        StablecoinState,
+    },
+};
+
+#[test]
+fn mint_quote_matches_the_mint() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+
+    let ix = env.quote_mint_ix(&coin, &alice, 300 * ONE);
+    env.process(&ix).expect("quote mint");
+    let quote: MintQuote = env.return_data();
+    assert_eq!(quote.mint_amount, 200 * ONE);
+    assert_eq!(quote.position_ratio, 15_000);
+
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+    assert_eq!(env.token_balance(&TestEnv::user_stablecoin_account(&alice, &coin)), quote.mint_amount);
+}
+
+#[test]
+fn burn_quote_includes_the_accrued_stability_fee() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    env.warp(YEAR_IN_SECONDS);
+    env.refresh_oracle(&env.bond_oracle.clone());
+
+    // The position owes 210 once a year of fees accrues, so 100 frees 300 * 100 / 210
+    let ix = env.quote_burn_ix(&coin, &alice, 100 * ONE);
+    env.process(&ix).expect("quote burn");
+    let quote: BurnQuote = env.return_data();
+    assert_eq!(quote.bond_amount, 300 * ONE * 100 / 210);
+
+    let bonds_before = env.token_balance(&alice_bonds);
+    let ix = env.burn_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    env.process(&ix).expect("burn");
+    assert_eq!(env.token_balance(&alice_bonds) - bonds_before, quote.bond_amount);
+}
+
+#[test]
+fn quotes_reject_denylisted_holders() {
+    let mut env = TestEnv::new();
+    let coin = env
+        .create_stablecoin_with("USDC", "USD", None, None, true)
+        .expect("create stablecoin");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let ix = env.add_to_denylist_ix(&coin, &alice);
+    env.process(&ix).expect("denylist");
+
+    let ix = env.quote_mint_ix(&coin, &alice, 300 * ONE);
+    assert_error(env.process(&ix), StablecoinError::AddressDenylisted);
+    let ix = env.quote_burn_ix(&coin, &alice, 100 * ONE);
+    assert_error(env.process(&ix), StablecoinError::AddressDenylisted);
+}
+
+#[test]
+fn mint_quote_applies_the_mint_policy() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, _) = env.new_user(1_000 * ONE);
+
+    let ix = env.set_mint_policy_ix(&coin, MintPolicy::AllowlistOnly);
+    env.process(&ix).expect("set mint policy");
+
+    let ix = env.quote_mint_ix(&coin, &alice, 300 * ONE);
+    assert_error(env.process(&ix), StablecoinError::MinterNotAllowlisted);
+
+    let ix = env.add_minter_ix(&coin, &alice);
+    env.process(&ix).expect("add minter");
+    let ix = env.quote_mint_ix(&coin, &alice, 300 * ONE);
+    env.process(&ix).expect("quote mint");
+}
+
+#[test]
+fn quotes_apply_the_flow_limits() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let limits = FlowLimits {
+        window_duration: 3_600,
+        max_net_mint: 100 * ONE,
+        user_max_net_redeem: 50 * ONE,
+        ..FlowLimits::default()
+    };
+    let ix = env.set_flow_limits_ix(&coin, limits);
+    env.process(&ix).expect("set flow limits");
+
+    let ix = env.quote_mint_ix(&coin, &alice, 300 * ONE);
+    assert_error(env.process(&ix), StablecoinError::FlowLimitExceeded);
+    let ix = env.quote_mint_ix(&coin, &alice, 150 * ONE);
+    env.process(&ix).expect("quote within the cap");
+
+    let ix = env.quote_burn_ix(&coin, &alice, 60 * ONE);
+    assert_error(env.process(&ix), StablecoinError::UserFlowLimitExceeded);
+}
+
+#[test]
+fn yield_quote_reports_the_position() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+
+    let ix = env.quote_yield_ix(&coin, &alice);
+    assert_error(env.process(&ix), StablecoinError::NoUserPosition);
+
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let ix = env.quote_yield_ix(&coin, &alice);
+    env.process(&ix).expect("quote yield");
+    let quote: YieldQuote = env.return_data();
+    assert_eq!(quote.position_ratio, 15_000);
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(quote.next_rebase_at, state.last_rebase + REBASE_INTERVAL);
+}
+
+#[test]
+fn flow_capacity_quote_reports_the_remaining_caps() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    let (bob, _) = env.new_user(0);
+
+    let limits = FlowLimits {
+        window_duration: 3_600,
+        max_net_mint: 500 * ONE,
+        user_max_net_mint: 250 * ONE,
+        ..FlowLimits::default()
+    };
+    let ix = env.set_flow_limits_ix(&coin, limits);
+    env.process(&ix).expect("set flow limits");
+    let opened_at = env.now();
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let ix = env.quote_flow_capacity_ix(&coin, &alice);
+    env.process(&ix).expect("quote flow capacity");
+    let capacity: FlowCapacity = env.return_data();
+    assert_eq!(capacity.mint_remaining, 300 * ONE);
+    assert_eq!(capacity.user_mint_remaining, 50 * ONE);
+    assert_eq!(capacity.redeem_remaining, u64::MAX);
+    assert_eq!(capacity.window_ends_at, opened_at + 3_600);
+    assert_eq!(capacity.user_window_ends_at, opened_at + 3_600);
+
+    // A holder with no state yet has the full per-holder cap
+    let ix = env.quote_flow_capacity_ix(&coin, &bob);
+    env.process(&ix).expect("quote flow capacity");
+    let capacity: FlowCapacity = env.return_data();
+    assert_eq!(capacity.user_mint_remaining, 250 * ONE);
+    assert_eq!(capacity.user_window_ends_at, 0);
+
+    env.warp(3_600);
+    let ix = env.quote_flow_capacity_ix(&coin, &alice);
+    env.process(&ix).expect("quote flow capacity");
+    let capacity: FlowCapacity = env.return_data();
+    assert_eq!(capacity.mint_remaining, 500 * ONE);
+    assert_eq!(capacity.window_ends_at, 0);
+}
@@ -0,0 +1,130 @@
+// tests/redemption.rs
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use solana_stablecoin::{
+    errors::StablecoinError,
+    events::StablecoinRedeemed,
+    states::{stablecoin::RedemptionMode, StablecoinState, UserState},
+};
+
+/// Position debt must add up to the coin's supply after every redemption
+fn assert_debt_matches_supply(env: &TestEnv, coin: &Coin) {
+    let state: StablecoinState = env.state(&coin.state);
+    let debt: u64 = state.user_shares.iter().map(|share| share.mint_amount).sum();
+    assert_eq!(debt, state.total_supply);
+}
+
+#[test]
+fn pro_rata_redemption_shrinks_every_position() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    let (bob, bob_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+    env.mint(&coin, &bob, &bob_bonds, 600 * ONE).expect("mint");
+
+    // A holder without a position, paid by Alice
+    let (carol, carol_bonds) = env.new_user(0);
+    env.move_stablecoins(&coin, &alice, &carol, 150 * ONE);
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 150 * ONE, RedemptionMode::ProRata, 0);
+    env.process(&ix).expect("redeem");
+
+    // 150 of face value at a bond price of 1.0, less the fee
+    let event = &env.events::<StablecoinRedeemed>()[0];
+    assert_eq!(event.bond_amount + event.fee_amount, 150 * ONE);
+    assert_eq!(env.token_balance(&carol_bonds), event.bond_amount);
+    assert_eq!(env.token_balance(&TestEnv::wallet_stablecoin_account(&carol, &coin)), 0);
+
+    // Redeeming a quarter of the debt takes a quarter from each position
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.total_supply, 450 * ONE);
+    assert_eq!(state.total_collateral, 750 * ONE);
+    assert_eq!(env.mint_supply(&coin.mint), 450 * ONE);
+    let positions: Vec<(u64, u64)> = state.user_shares
+        .iter()
+        .map(|share| (share.bond_amount, share.mint_amount))
+        .collect();
+    assert_eq!(positions, vec![(250 * ONE, 150 * ONE), (500 * ONE, 300 * ONE)]);
+    assert_debt_matches_supply(&env, &coin);
+}
+
+#[test]
+fn holders_who_never_minted_redeem_from_their_own_account() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let (carol, carol_bonds) = env.new_user(0);
+    env.move_stablecoins(&coin, &alice, &carol, 100 * ONE);
+    assert!(!env.exists(&TestEnv::user_state(&carol, &coin)));
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 100 * ONE, RedemptionMode::LowestHealth, 0);
+    env.process(&ix).expect("redeem");
+    assert_eq!(env.token_balance(&TestEnv::wallet_stablecoin_account(&carol, &coin)), 0);
+    assert!(env.token_balance(&carol_bonds) > 0);
+
+    // The redemption opens a user state to track Carol's flow
+    let state: UserState = env.state(&TestEnv::user_state(&carol, &coin));
+    assert_eq!((state.user, state.stablecoin), (carol, coin.mint));
+}
+
+/// Alice at 150% and Bob at 225%, with Carol holding 100 of Bob's coins
+fn two_positions_and_a_holder(env: &mut TestEnv, coin: &Coin) -> (Pubkey, Pubkey) {
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    let (bob, bob_bonds) = env.new_user(1_000 * ONE);
+    env.mint(coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+    env.mint(coin, &bob, &bob_bonds, 600 * ONE).expect("mint");
+    let ix = env.deposit_ix(coin, &bob, &bob_bonds, 300 * ONE);
+    env.process(&ix).expect("deposit");
+
+    let (carol, carol_bonds) = env.new_user(0);
+    env.move_stablecoins(coin, &bob, &carol, 100 * ONE);
+    (carol, carol_bonds)
+}
+
+#[test]
+fn redeem_draws_face_value_from_the_riskiest_position() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (carol, carol_bonds) = two_positions_and_a_holder(&mut env, &coin);
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 100 * ONE, RedemptionMode::LowestHealth, 0);
+    env.process(&ix).expect("redeem");
+
+    // 100 of face value at a bond price of 1.0, less the fee
+    let event = &env.events::<StablecoinRedeemed>()[0];
+    assert_eq!(event.bond_amount + event.fee_amount, 100 * ONE);
+    assert!(event.fee_amount > 0);
+    assert_eq!(env.token_balance(&carol_bonds), event.bond_amount);
+    assert_eq!(env.token_balance(&env.bond_fee_vault()), event.fee_amount);
+
+    // Only Alice's position paid
+    let state: StablecoinState = env.state(&coin.state);
+    let positions: Vec<(u64, u64)> = state.user_shares
+        .iter()
+        .map(|share| (share.bond_amount, share.mint_amount))
+        .collect();
+    assert_eq!(positions, vec![(200 * ONE, 100 * ONE), (900 * ONE, 400 * ONE)]);
+    assert_eq!(state.total_supply, 500 * ONE);
+    assert_eq!(state.total_collateral, 1_100 * ONE);
+    assert_debt_matches_supply(&env, &coin);
+}
+
+#[test]
+fn redeem_honors_the_minimum_bonds_out() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (carol, carol_bonds) = two_positions_and_a_holder(&mut env, &coin);
+
+    // The fee comes out of the 100 bonds, so asking for all of them fails
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 100 * ONE, RedemptionMode::ProRata, 100 * ONE);
+    assert_error(env.process(&ix), StablecoinError::ExcessiveSlippage);
+
+    let ix = env.redeem_ix(&coin, &carol, &carol_bonds, 100 * ONE, RedemptionMode::ProRata, 99 * ONE);
+    env.process(&ix).expect("redeem");
+    assert_debt_matches_supply(&env, &coin);
+}
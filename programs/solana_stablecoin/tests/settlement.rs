@@ -0,0 +1,86 @@
+// tests/settlement.rs
+mod common;
+
+use common::*;
+use solana_stablecoin::{
+    errors::StablecoinError,
+    events::{StablecoinSettled, StablecoinShutdown},
+    states::StablecoinState,
+};
+
+#[test]
+fn holders_settle_at_the_frozen_rate() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    let (bob, bob_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+    env.mint(&coin, &bob, &bob_bonds, 600 * ONE).expect("mint");
+
+    let ix = env.shutdown_ix(&coin);
+    env.process(&ix).expect("shutdown");
+
+    // 900 bonds behind 600 stablecoins
+    let shutdown = &env.events::<StablecoinShutdown>()[0];
+    assert_eq!(shutdown.settlement_rate, 1_500_000);
+    assert_eq!(shutdown.settlement_price, 1_000_000);
+
+    let ix = env.settle_ix(&coin, &alice, &alice_bonds, 200 * ONE);
+    env.process(&ix).expect("settle");
+    assert_eq!(env.events::<StablecoinSettled>()[0].bond_amount, 300 * ONE);
+    assert_eq!(env.token_balance(&alice_bonds), 1_000 * ONE);
+
+    let ix = env.settle_ix(&coin, &bob, &bob_bonds, 400 * ONE);
+    env.process(&ix).expect("settle");
+    assert_eq!(env.token_balance(&bob_bonds), 1_000 * ONE);
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.total_supply, 0);
+    assert_eq!(state.total_collateral, 0);
+    assert_eq!(env.token_balance(&coin.collateral_vault), 0);
+    assert_eq!(env.mint_supply(&coin.mint), 0);
+}
+
+#[test]
+fn holders_who_never_minted_settle_from_their_own_account() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let (carol, carol_bonds) = env.new_user(0);
+    env.move_stablecoins(&coin, &alice, &carol, 100 * ONE);
+
+    let ix = env.shutdown_ix(&coin);
+    env.process(&ix).expect("shutdown");
+
+    let ix = env.settle_ix(&coin, &carol, &carol_bonds, 100 * ONE);
+    env.process(&ix).expect("settle");
+    assert_eq!(env.token_balance(&carol_bonds), 150 * ONE);
+    assert_eq!(env.token_balance(&TestEnv::wallet_stablecoin_account(&carol, &coin)), 0);
+    assert!(!env.exists(&TestEnv::user_state(&carol, &coin)));
+}
+
+#[test]
+fn settlement_needs_a_shutdown() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let ix = env.settle_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    assert_error(env.process(&ix), StablecoinError::StablecoinNotShutdown);
+}
+
+#[test]
+fn shutdown_disables_minting() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let ix = env.shutdown_ix(&coin);
+    env.process(&ix).expect("shutdown");
+
+    assert_error(env.mint(&coin, &alice, &alice_bonds, 300 * ONE), StablecoinError::StablecoinIsShutdown);
+}
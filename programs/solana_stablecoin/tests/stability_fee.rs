@@ -0,0 +1,250 @@
+// tests/stability_fee.rs
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use solana_stablecoin::{
+    constants::{MAX_STABILITY_FEE_BPS, YEAR_IN_SECONDS},
+    errors::StablecoinError,
+    events::{DebtRepaid, StablecoinShutdown},
+    states::{stablecoin::RedemptionMode, StablecoinState},
+};
+
+/// Advances a year, over which the fixtures' 5% fee adds 10 to a 200 position
+fn warp_a_year(env: &mut TestEnv) {
+    env.warp(YEAR_IN_SECONDS);
+    env.refresh_oracle(&env.bond_oracle.clone());
+}
+
+#[test]
+fn withdrawals_check_health_against_accrued_debt() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+    let ix = env.deposit_ix(&coin, &alice, &alice_bonds, 100 * ONE);
+    env.process(&ix).expect("deposit");
+
+    warp_a_year(&mut env);
+
+    // 310 bonds would back the original 200 but not the accrued 210
+    let ix = env.withdraw_ix(&coin, &alice, &alice_bonds, 90 * ONE);
+    assert_error(env.process(&ix), StablecoinError::CollateralRatioTooLow);
+
+    let ix = env.withdraw_ix(&coin, &alice, &alice_bonds, 85 * ONE);
+    env.process(&ix).expect("withdraw");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.user_shares[0].mint_amount, 210 * ONE);
+    assert_eq!(state.total_supply, 210 * ONE);
+    assert_eq!(env.token_balance(&env.stability_fee_vault(&coin)), 10 * ONE);
+}
+
+#[test]
+fn redemptions_accrue_the_stability_fee_first() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    warp_a_year(&mut env);
+
+    let ix = env.redeem_ix(&coin, &alice, &alice_bonds, 100 * ONE, RedemptionMode::LowestHealth, 0);
+    env.process(&ix).expect("redeem");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.total_stability_fees, 10 * ONE);
+    assert_eq!(state.user_shares[0].mint_amount, 110 * ONE);
+    assert_eq!(state.total_supply, 110 * ONE);
+    assert_eq!(env.mint_supply(&coin.mint), 110 * ONE);
+}
+
+#[test]
+fn shutdown_counts_accrued_fees_in_the_settlement_rate() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    warp_a_year(&mut env);
+
+    let ix = env.shutdown_ix(&coin);
+    env.process(&ix).expect("shutdown");
+
+    // 300 bonds behind 210 stablecoins, 10 of them in the fee vault
+    let shutdown = &env.events::<StablecoinShutdown>()[0];
+    assert_eq!(shutdown.total_supply, 210 * ONE);
+    assert_eq!(shutdown.settlement_rate, 300 * 1_000_000 / 210);
+    assert_eq!(env.token_balance(&env.stability_fee_vault(&coin)), 10 * ONE);
+}
+
+#[test]
+fn another_holder_can_repay_accrued_debt() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    let (bob, bob_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    warp_a_year(&mut env);
+    env.mint(&coin, &bob, &bob_bonds, 30 * ONE).expect("mint");
+
+    // Alice owes 210 but only ever received 200
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.user_shares[0].mint_amount, 210 * ONE);
+
+    let ix = env.repay_debt_ix(&coin, &bob, &alice, 10 * ONE);
+    env.process(&ix).expect("repay");
+    let repaid = &env.events::<DebtRepaid>()[0];
+    assert_eq!((repaid.owner, repaid.remaining_debt), (alice, 200 * ONE));
+    assert_eq!(env.token_balance(&TestEnv::user_stablecoin_account(&bob, &coin)), 10 * ONE);
+
+    // Alice's own stablecoins now close her position
+    let ix = env.burn_ix(&coin, &alice, &alice_bonds, 200 * ONE);
+    env.process(&ix).expect("burn");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert!(state.user_shares.iter().all(|share| share.owner != alice));
+    assert_eq!(env.token_balance(&alice_bonds), 1_000 * ONE);
+    assert_eq!(state.total_supply, env.mint_supply(&coin.mint));
+}
+
+#[test]
+fn repayment_is_capped_by_the_position_debt() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    let (bob, bob_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 30 * ONE).expect("mint");
+    env.mint(&coin, &bob, &bob_bonds, 300 * ONE).expect("mint");
+
+    let ix = env.repay_debt_ix(&coin, &bob, &alice, 21 * ONE);
+    assert_error(env.process(&ix), StablecoinError::InvalidRepayAmount);
+
+    let ix = env.repay_debt_ix(&coin, &bob, &Pubkey::new_unique(), ONE);
+    assert_error(env.process(&ix), StablecoinError::NoUserPosition);
+}
+
+#[test]
+fn withdrawn_fees_settle_after_a_shutdown() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    warp_a_year(&mut env);
+    let ix = env.shutdown_ix(&coin);
+    env.process(&ix).expect("shutdown");
+
+    let treasury = Pubkey::new_unique();
+    let ix = env.withdraw_stability_fees_ix(&coin, &treasury, 11 * ONE);
+    assert_error(env.process(&ix), StablecoinError::InvalidFeeWithdrawal);
+
+    let ix = env.withdraw_stability_fees_ix(&coin, &treasury, 10 * ONE);
+    env.process(&ix).expect("withdraw fees");
+    assert_eq!(env.token_balance(&env.stability_fee_vault(&coin)), 0);
+    assert_eq!(env.token_balance(&TestEnv::user_stablecoin_account(&treasury, &coin)), 10 * ONE);
+
+    let treasury_bonds = Pubkey::new_unique();
+    env.create_token_account(&treasury_bonds, &env.bond_mint.clone(), &treasury, 0);
+    env.airdrop(&treasury, ONE);
+    let ix = env.settle_ix(&coin, &treasury, &treasury_bonds, 10 * ONE);
+    env.process(&ix).expect("settle fees");
+
+    let ix = env.settle_ix(&coin, &alice, &alice_bonds, 200 * ONE);
+    env.process(&ix).expect("settle");
+
+    // Both claims together drain the vault, up to the settlement rate's rounding
+    assert!(env.token_balance(&coin.collateral_vault) < 1_000);
+    let rate = env.state::<StablecoinState>(&coin.state).settlement_rate;
+    assert_eq!(env.token_balance(&treasury_bonds), 10 * rate);
+    assert_eq!(env.mint_supply(&coin.mint), 0);
+}
+
+#[test]
+fn only_the_admin_withdraws_stability_fees() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+    warp_a_year(&mut env);
+    env.mint(&coin, &alice, &alice_bonds, 3 * ONE).expect("mint");
+
+    let mut ix = env.withdraw_stability_fees_ix(&coin, &alice, ONE);
+    ix.accounts[0].pubkey = alice;
+    assert_error(env.process(&ix), StablecoinError::Unauthorized);
+}
+
+#[test]
+fn anyone_can_accrue_the_stability_fee() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    let (keeper, _) = env.new_user(0);
+    env.airdrop(&keeper, LAMPORTS_PER_SOL);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    warp_a_year(&mut env);
+
+    let ix = env.accrue_stability_fee_ix(&coin, &keeper);
+    env.process(&ix).expect("accrue");
+    assert_eq!(env.token_balance(&env.stability_fee_vault(&coin)), 10 * ONE);
+
+    // Nothing more accrues until a full interval has passed
+    env.warp(60);
+    env.process(&ix).expect("accrue");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.user_shares[0].mint_amount, 210 * ONE);
+    assert_eq!(state.total_stability_fees, 10 * ONE);
+    assert_eq!(env.mint_supply(&coin.mint), 210 * ONE);
+}
+
+#[test]
+fn rate_changes_settle_the_old_rate_first() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    let ix = env.set_stability_fee_ix(&coin, MAX_STABILITY_FEE_BPS + 1);
+    assert_error(env.process(&ix), StablecoinError::InvalidStabilityFee);
+
+    warp_a_year(&mut env);
+
+    let ix = env.set_stability_fee_ix(&coin, 0);
+    env.process(&ix).expect("set stability fee");
+    assert_eq!(env.token_balance(&env.stability_fee_vault(&coin)), 10 * ONE);
+
+    warp_a_year(&mut env);
+
+    let ix = env.accrue_stability_fee_ix(&coin, &alice);
+    env.process(&ix).expect("accrue");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.stability_fee_bps, 0);
+    assert_eq!(state.user_shares[0].mint_amount, 210 * ONE);
+    assert_eq!(env.token_balance(&env.stability_fee_vault(&coin)), 10 * ONE);
+}
+
+#[test]
+fn rate_changes_inside_an_interval_charge_the_old_rate() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+    let (alice, alice_bonds) = env.new_user(1_000 * ONE);
+    env.mint(&coin, &alice, &alice_bonds, 300 * ONE).expect("mint");
+
+    warp_a_year(&mut env);
+    let ix = env.accrue_stability_fee_ix(&coin, &alice);
+    env.process(&ix).expect("accrue");
+
+    // Half an interval at 5% on 210, too short for a regular accrual
+    env.warp(1_800);
+    let ix = env.set_stability_fee_ix(&coin, 0);
+    env.process(&ix).expect("set stability fee");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.user_shares[0].mint_amount, 210 * ONE + 600);
+    assert_eq!(state.total_stability_fees, 10 * ONE + 600);
+    assert_eq!(env.token_balance(&env.stability_fee_vault(&coin)), 10 * ONE + 600);
+}
@@ -0,0 +1,95 @@
+// tests/stablecoin.rs
+mod common;
+
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions, StateWithExtensions},
+};
+use common::*;
+use solana_stablecoin::{
+    errors::StablecoinError,
+    events::StablecoinCreated,
+    states::{FactoryState, StablecoinState, SymbolRecord, Token2022Config, UserState},
+};
+
+#[test]
+fn create_stablecoin_registers_symbol_and_creator_state() {
+    let mut env = TestEnv::new();
+    let coin = env.create_stablecoin("USDX");
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.symbol, "USDX");
+    assert_eq!(state.mint, coin.mint);
+    assert_eq!(state.factory, env.factory_state);
+    assert_eq!(state.collateral_vault, coin.collateral_vault);
+    assert!(state.is_current());
+
+    let record: SymbolRecord = env.state(&env.symbol_record("USDX"));
+    assert_eq!(record.stablecoin, coin.state);
+
+    let creator_state: UserState = env.state(&TestEnv::user_state(&coin.creator, &coin));
+    assert_eq!(creator_state.user, coin.creator);
+    assert_eq!(creator_state.stablecoin, coin.mint);
+
+    let factory: FactoryState = env.state(&env.factory_state);
+    assert_eq!(factory.stablecoin_count, 1);
+    assert_eq!(env.events::<StablecoinCreated>().len(), 1);
+}
+
+#[test]
+fn create_stablecoin_rejects_taken_symbol() {
+    let mut env = TestEnv::new();
+    env.create_stablecoin("USDX");
+
+    // The symbol record already exists, so its init fails
+    let result = env.create_stablecoin_with("USDX", "USD", None, None, false);
+    assert!(result.is_err());
+
+    let result = env.create_stablecoin_with("usdx", "USD", None, None, false);
+    assert_error(result.map(|_| ()), StablecoinError::InvalidSymbolFormat);
+}
+
+#[test]
+fn create_token_2022_stablecoin_in_compliance_mode() {
+    let mut env = TestEnv::new();
+    let config = Token2022Config { permanent_delegate: true, ..Token2022Config::default() };
+    let coin = env
+        .create_stablecoin_with("EURX", "USD", None, Some(config), true)
+        .expect("create stablecoin");
+
+    let mint = env.account(&coin.mint).unwrap();
+    assert_eq!(mint.owner, spl_token_2022::ID);
+
+    let state: StablecoinState = env.state(&coin.state);
+    assert_eq!(state.token_program, spl_token_2022::ID);
+    assert!(state.compliance_mode);
+}
+
+#[test]
+fn initialize_factory_only_once() {
+    let mut env = TestEnv::new();
+    let ix = env.initialize_factory_ix(FACTORY_ID, 15_000, 100);
+    assert!(env.process(&ix).is_err());
+}
+
+#[test]
+fn authority_updates_the_interest_rate() {
+    let mut env = TestEnv::new();
+    let config = Token2022Config { interest_rate: Some(100), ..Token2022Config::default() };
+    let coin = env
+        .create_stablecoin_with("EURX", "USD", None, Some(config), false)
+        .expect("create stablecoin");
+
+    let ix = env.update_interest_rate_ix(&coin, 250);
+    env.process(&ix).expect("update interest rate");
+
+    let data = &env.account(&coin.mint).unwrap().data;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data).unwrap();
+    let rate = mint.get_extension::<InterestBearingConfig>().unwrap().current_rate;
+    assert_eq!(i16::from(rate), 250);
+
+    // Classic SPL mints have no interest-bearing extension
+    let classic = env.create_stablecoin("USDX");
+    let ix = env.update_interest_rate_ix(&classic, 250);
+    assert_error(env.process(&ix), StablecoinError::InvalidTokenProgram);
+}